@@ -0,0 +1,431 @@
+/*
+ * Copyright © 2024 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// attribute
+
+// ----------------------------------------------------------------
+
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::{
+    parse_quote, Attribute, Expr, ExprLit, Field, Generics, Ident, Lit, LitStr, Token, Visibility,
+};
+
+// ----------------------------------------------------------------
+
+pub(crate) const ATTR_SKIP: &str = "skip";
+pub(crate) const ATTR_ONLY: &str = "only";
+pub(crate) const ATTR_VIS: &str = "vis";
+/// `#[default = literal]` / `#[default(expr)]`, shared by `NoArgsConstructor`, `RequiredArgsConstructor` and `Builder`.
+pub(crate) const ATTR_DEFAULT: &str = "default";
+
+/// Fields added by the attribute macros, e.g. `#[track_changes]`, are named `__lombok_x`.
+pub(crate) const HIDDEN_FIELD_PREFIX: &str = "__lombok";
+
+// ----------------------------------------------------------------
+
+/// The value a helper attribute key accepts.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ArgShape {
+    /// `key`, `key = true|false`
+    Flag,
+    /// `key = "..."`
+    Str,
+    /// `key = value`
+    Expr,
+    /// `key`, `key = true|false`, `key = "..."`
+    FlagOrStr,
+    /// `key`, `key = value`
+    FlagOrExpr,
+}
+
+/// A key accepted by a helper attribute.
+///
+/// - `(ATTR_VIS, ArgShape::Str)`
+pub(crate) type AttributeKey = (&'static str, ArgShape);
+
+/// `#[x(skip)]` and `#[x(only)]`, see [`try_select_fields`].
+pub(crate) const SELECTION_KEYS: &[AttributeKey] =
+    &[(ATTR_SKIP, ArgShape::Flag), (ATTR_ONLY, ArgShape::Flag)];
+/// `#[x(vis = "...")]`, see [`AttributeArgs::try_vis_or`].
+pub(crate) const VIS_KEYS: &[AttributeKey] = &[(ATTR_VIS, ArgShape::Str)];
+
+// ----------------------------------------------------------------
+
+/// A single `key` or `key = value` entry of a helper attribute.
+///
+/// - `#[getter(deref)]`
+/// - `#[builder(method = "activity")]`
+/// - `#[getter(lazy = path::to::init_fn)]`
+pub(crate) struct AttributeArg {
+    pub(crate) key: Ident,
+    pub(crate) value: Option<Expr>,
+}
+
+impl Parse for AttributeArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // `mut`, `const`, ... are keywords, but valid keys.
+        let key = input.call(Ident::parse_any)?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        Ok(Self { key, value })
+    }
+}
+
+impl AttributeArg {
+    /// Try to check the key against the `keys` of the `#[name(...)]` attribute, and the shape of the value.
+    fn try_check(&self, name: &str, keys: &[&[AttributeKey]]) -> syn::Result<()> {
+        let key = &self.key;
+        let Some(shape) = keys
+            .iter()
+            .flat_map(|keys| keys.iter())
+            .find(|(accepted, _)| key == accepted)
+            .map(|(_, shape)| *shape)
+        else {
+            let accepted: Vec<String> = keys
+                .iter()
+                .flat_map(|keys| keys.iter())
+                .map(|(accepted, _)| format!("`{}`", accepted))
+                .collect();
+            return Err(syn::Error::new_spanned(
+                key,
+                format!(
+                    "unknown `{}` option `{}`, expected one of {}",
+                    name,
+                    key,
+                    accepted.join(", ")
+                ),
+            ));
+        };
+
+        let flag = matches!(
+            self.value,
+            None | Some(Expr::Lit(ExprLit {
+                lit: Lit::Bool(_),
+                ..
+            }))
+        );
+        let str = matches!(
+            self.value,
+            Some(Expr::Lit(ExprLit {
+                lit: Lit::Str(_),
+                ..
+            }))
+        );
+        let (valid, expected) = match shape {
+            ArgShape::Flag => (flag, format!("`{}` or `{} = true|false`", key, key)),
+            ArgShape::Str => (str, format!(r#"`{} = "..."`"#, key)),
+            ArgShape::Expr => (self.value.is_some(), format!("`{} = ...`", key)),
+            ArgShape::FlagOrStr => (
+                flag || str,
+                format!(r#"`{}`, `{} = true|false` or `{} = "..."`"#, key, key, key),
+            ),
+            ArgShape::FlagOrExpr => (true, String::new()),
+        };
+        if !valid {
+            return Err(syn::Error::new_spanned(
+                key,
+                format!("expected {}", expected),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// All entries of the helper attributes with the same name, e.g. every `#[getter(...)]` on a field.
+#[derive(Default)]
+pub(crate) struct AttributeArgs {
+    args: Vec<AttributeArg>,
+}
+
+impl AttributeArgs {
+    /// Try to parse and merge every `#[name(...)]` attribute in `attrs`.
+    ///
+    /// A bare `#[name]` is accepted and yields no entries. A key missing from `keys`,
+    /// or a value of the wrong shape, is an error.
+    pub(crate) fn try_parse(
+        name: &str,
+        keys: &[&[AttributeKey]],
+        attrs: &[Attribute],
+    ) -> syn::Result<Self> {
+        let mut args = Vec::new();
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident(name)) {
+            if attr.tokens.is_empty() {
+                continue;
+            }
+
+            let nested =
+                attr.parse_args_with(Punctuated::<AttributeArg, Token![,]>::parse_terminated)?;
+            for arg in nested.iter() {
+                arg.try_check(name, keys)?;
+            }
+            args.extend(nested);
+        }
+
+        Ok(Self { args })
+    }
+
+    /// Try to parse the arguments of an attribute macro, e.g. `capacity = 64` of `#[history(capacity = 64)]`.
+    pub(crate) fn try_parse_args(
+        name: &str,
+        keys: &[&[AttributeKey]],
+        tokens: proc_macro2::TokenStream,
+    ) -> syn::Result<Self> {
+        let args = Punctuated::<AttributeArg, Token![,]>::parse_terminated.parse2(tokens)?;
+        for arg in args.iter() {
+            arg.try_check(name, keys)?;
+        }
+
+        Ok(Self {
+            args: args.into_iter().collect(),
+        })
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<&AttributeArg> {
+        self.args.iter().rev().find(|arg| arg.key == key)
+    }
+
+    /// `#[x(key)]` and `#[x(key = true)]` are `Some(true)`, `#[x(key = false)]` is `Some(false)`.
+    pub(crate) fn try_flag(&self, key: &str) -> syn::Result<Option<bool>> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(AttributeArg { value: None, .. }) => Ok(Some(true)),
+            Some(AttributeArg {
+                value:
+                    Some(Expr::Lit(ExprLit {
+                        lit: Lit::Bool(flag),
+                        ..
+                    })),
+                ..
+            }) => Ok(Some(flag.value)),
+            Some(AttributeArg { key, .. }) => Err(syn::Error::new_spanned(
+                key,
+                format!("expected `{}` or `{} = true|false`", key, key),
+            )),
+        }
+    }
+
+    /// `#[x(key = "...")]`
+    pub(crate) fn try_str(&self, key: &str) -> syn::Result<Option<LitStr>> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(AttributeArg {
+                value:
+                    Some(Expr::Lit(ExprLit {
+                        lit: Lit::Str(value),
+                        ..
+                    })),
+                ..
+            }) => Ok(Some(value.clone())),
+            Some(AttributeArg { key, .. }) => Err(syn::Error::new_spanned(
+                key,
+                format!(r#"expected `{} = "..."`"#, key),
+            )),
+        }
+    }
+
+    /// `#[x(key = value)]`, e.g. `#[getter(lazy = path::to::init_fn)]`.
+    pub(crate) fn try_expr(&self, key: &str) -> syn::Result<Option<&Expr>> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(AttributeArg {
+                value: Some(value), ..
+            }) => Ok(Some(value)),
+            Some(AttributeArg { key, .. }) => Err(syn::Error::new_spanned(
+                key,
+                format!("expected `{} = ...`", key),
+            )),
+        }
+    }
+
+    /// Like [`AttributeArgs::try_str`], falling back to the struct-level `fallback`.
+    pub(crate) fn try_str_or(
+        &self,
+        fallback: &AttributeArgs,
+        key: &str,
+    ) -> syn::Result<Option<LitStr>> {
+        match self.try_str(key)? {
+            Some(value) => Ok(Some(value)),
+            None => fallback.try_str(key),
+        }
+    }
+
+    /// `#[x(vis = "pub(crate)")]`, falling back to the struct-level `fallback` and then to `inherited`.
+    ///
+    /// `vis = ""` is private.
+    pub(crate) fn try_vis_or(
+        &self,
+        fallback: &AttributeArgs,
+        inherited: &Visibility,
+    ) -> syn::Result<Visibility> {
+        match self.try_str_or(fallback, ATTR_VIS)? {
+            Some(vis) => vis.parse(),
+            None => Ok(inherited.clone()),
+        }
+    }
+
+    /// Like [`AttributeArgs::try_flag`], falling back to the struct-level `fallback` and then to `false`.
+    ///
+    /// - `#[getter(deref)]` on the struct, `#[getter(deref = false)]` on a field.
+    pub(crate) fn try_flag_or(&self, fallback: &AttributeArgs, key: &str) -> syn::Result<bool> {
+        self.try_flag_or_else(fallback, key, false)
+    }
+
+    /// Like [`AttributeArgs::try_flag_or`], falling back to `default` instead of `false`.
+    ///
+    /// - `bool` fields are `#[getter(predicate)]` unless said otherwise.
+    pub(crate) fn try_flag_or_else(
+        &self,
+        fallback: &AttributeArgs,
+        key: &str,
+        default: bool,
+    ) -> syn::Result<bool> {
+        match self.try_flag(key)? {
+            Some(flag) => Ok(flag),
+            None => Ok(fallback.try_flag(key)?.unwrap_or(default)),
+        }
+    }
+}
+
+// ----------------------------------------------------------------
+
+/// Predicate that the field was added by an attribute macro and is no accessor's business.
+pub(crate) fn try_predicate_is_hidden_field(field: &Field) -> bool {
+    field
+        .ident
+        .as_ref()
+        .is_some_and(|ident| ident.to_string().starts_with(HIDDEN_FIELD_PREFIX))
+}
+
+/// The comparison impls of the type of a hidden field: every value is equal and hashes nothing.
+///
+/// A `#[derive(PartialEq, Hash, ..)]` of the user's struct then compares the visible fields only.
+pub(crate) fn derive_hidden_field_impls(
+    type_name: &Ident,
+    generics: &Generics,
+) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::std::cmp::PartialEq for #type_name #ty_generics #where_clause {
+            fn eq(&self, _: &Self) -> bool {
+                true
+            }
+        }
+
+        impl #impl_generics ::std::cmp::Eq for #type_name #ty_generics #where_clause {}
+
+        impl #impl_generics ::std::cmp::PartialOrd for #type_name #ty_generics #where_clause {
+            fn partial_cmp(&self, other: &Self) -> ::std::option::Option<::std::cmp::Ordering> {
+                ::std::option::Option::Some(::std::cmp::Ord::cmp(self, other))
+            }
+        }
+
+        impl #impl_generics ::std::cmp::Ord for #type_name #ty_generics #where_clause {
+            fn cmp(&self, _: &Self) -> ::std::cmp::Ordering {
+                ::std::cmp::Ordering::Equal
+            }
+        }
+
+        impl #impl_generics ::std::hash::Hash for #type_name #ty_generics #where_clause {
+            fn hash<H: ::std::hash::Hasher>(&self, _: &mut H) {}
+        }
+    }
+}
+
+/// Try to parse the default value of a field.
+///
+/// - `#[default]` -> `Default::default()`, e.g. to mark a builder field as optional
+/// - `#[default(expr)]` -> `expr`
+/// - `#[default = 8080]` -> `8080`, attribute values must be literals
+/// - `#[default = "anonymous"]` -> `"anonymous".into()`, like `SmartDefault`
+pub(crate) fn try_parse_default(field: &Field) -> syn::Result<Option<Expr>> {
+    let Some(attr) = field
+        .attrs
+        .iter()
+        .rev()
+        .find(|attr| attr.path.is_ident(ATTR_DEFAULT))
+    else {
+        return Ok(None);
+    };
+
+    let parser = |input: ParseStream| {
+        if input.is_empty() {
+            return Ok(parse_quote!(::std::default::Default::default()));
+        }
+        if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            return match input.parse::<Lit>()? {
+                Lit::Str(value) => Ok(parse_quote!(::std::convert::Into::into(#value))),
+                value => Ok(parse_quote!(#value)),
+            };
+        }
+
+        let content;
+        syn::parenthesized!(content in input);
+        content.parse::<Expr>()
+    };
+    match parser.parse2(attr.tokens.clone()) {
+        Ok(default) => Ok(Some(default)),
+        Err(_) => Err(syn::Error::new_spanned(
+            attr,
+            "expected `#[default]`, `#[default = literal]` or `#[default(expr)]`",
+        )),
+    }
+}
+
+/// Try to parse the `#[name(...)]` attributes of every field, keeping the selected fields only.
+///
+/// - Hidden fields are dropped.
+/// - `#[name(skip)]` drops the field.
+/// - `#[name(only)]` on any field drops every field without it.
+pub(crate) fn try_select_fields<'a>(
+    name: &str,
+    keys: &[&[AttributeKey]],
+    fields: impl IntoIterator<Item = &'a Field>,
+) -> syn::Result<Vec<(&'a Field, AttributeArgs)>> {
+    let mut parsed_fields = Vec::new();
+    for field in fields {
+        if try_predicate_is_hidden_field(field) {
+            continue;
+        }
+        parsed_fields.push((field, AttributeArgs::try_parse(name, keys, &field.attrs)?));
+    }
+
+    let mut whitelist = false;
+    for (_, field_args) in parsed_fields.iter() {
+        whitelist |= field_args.try_flag(ATTR_ONLY)?.unwrap_or_default();
+    }
+
+    let mut selected_fields = Vec::new();
+    for (field, field_args) in parsed_fields {
+        let skip = field_args.try_flag(ATTR_SKIP)?.unwrap_or_default();
+        let only = field_args.try_flag(ATTR_ONLY)?.unwrap_or_default();
+        if !skip && (!whitelist || only) {
+            selected_fields.push((field, field_args));
+        }
+    }
+
+    Ok(selected_fields)
+}
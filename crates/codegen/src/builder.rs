@@ -20,174 +20,224 @@
 
 use proc_macro::TokenStream;
 
-use quote::{format_ident, quote};
-use syn::DeriveInput;
+use proc_macro2::TokenTree;
+use quote::{format_ident, quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::{parse_quote, DeriveInput, Field, GenericParam, Generics, Ident, Type, WherePredicate};
 use synext::*;
 
+use crate::attribute::{
+    try_parse_default, try_predicate_is_hidden_field, ArgShape, AttributeArg, AttributeArgs,
+    AttributeKey,
+};
+use crate::sensitive::Sensitive;
+
 // ----------------------------------------------------------------
 
 const BUILDER_SUFFIX: &str = "Builder";
 const BUILDER_ATTR_NAME: &str = "builder";
 const BUILDER_ATTR_CUSTOM_METHOD: &str = "method";
+const BUILDER_ATTR_TYPE_CHANGING: &str = "type_changing";
+const BUILDER_ATTR_DEFAULT: &str = "default";
+
+const BUILDER_KEYS: &[&[AttributeKey]] = &[&[
+    (BUILDER_ATTR_CUSTOM_METHOD, ArgShape::Str),
+    (BUILDER_ATTR_TYPE_CHANGING, ArgShape::FlagOrStr),
+    (BUILDER_ATTR_DEFAULT, ArgShape::FlagOrExpr),
+]];
 
 // ----------------------------------------------------------------
 
 pub fn derive_builder_fields(derive_input: &DeriveInput) -> Vec<proc_macro2::TokenStream> {
     let mut builder_fields = Vec::new();
-    let fields = try_parse_named_fields(&derive_input);
-    fields.iter().for_each(|field| {
-        let field_name = field.ident.clone().unwrap();
-        let field_type = &field.ty;
-
-        if try_predicate_is_option(field_type) {
-            let inner_type = try_unwrap_option(field_type);
-            let builder_field = quote! {
-                #field_name: ::std::option::Option<#inner_type>
-            };
-
-            builder_fields.push(builder_field);
-        } else if try_predicate_is_vec(field_type) {
-            let builder_field = quote! {
-                #field_name: #field_type
-            };
-
-            builder_fields.push(builder_field);
-        } else {
-            let builder_field = quote! {
-                #field_name: ::std::option::Option<#field_type>
-            };
-
-            builder_fields.push(builder_field);
-        }
-    });
+    let fields = try_parse_named_fields(derive_input);
+    fields
+        .iter()
+        .filter(|field| !try_predicate_is_hidden_field(field))
+        .for_each(|field| {
+            let field_name = field.ident.clone().unwrap();
+            let field_type = &field.ty;
+
+            if try_predicate_is_option(field_type) {
+                let inner_type = try_unwrap_option(field_type);
+                let builder_field = quote! {
+                    #field_name: ::std::option::Option<#inner_type>
+                };
+
+                builder_fields.push(builder_field);
+            } else if try_predicate_is_vec(field_type) && !try_predicate_has_default(field) {
+                let builder_field = quote! {
+                    #field_name: #field_type
+                };
+
+                builder_fields.push(builder_field);
+            } else {
+                let builder_field = quote! {
+                    #field_name: ::std::option::Option<#field_type>
+                };
+
+                builder_fields.push(builder_field);
+            }
+        });
 
     builder_fields
 }
 
-pub fn derive_builder_setters(derive_input: &DeriveInput) -> Vec<proc_macro2::TokenStream> {
+pub fn derive_builder_setters(
+    derive_input: &DeriveInput,
+    type_changing_params: &[(Ident, Ident)],
+) -> Vec<proc_macro2::TokenStream> {
     let mut builder_setters = Vec::new();
-    let fields = try_parse_named_fields(&derive_input);
-    fields.iter().for_each(|field| {
-        let field_name = field.ident.clone().unwrap();
-        let field_type = &field.ty;
+    let fields = try_parse_named_fields(derive_input);
+    fields
+        .iter()
+        .filter(|field| !try_predicate_is_hidden_field(field))
+        .for_each(|field| {
+            let field_name = field.ident.clone().unwrap();
+            let field_type = &field.ty;
+
+            if type_changing_params
+                .iter()
+                .any(|(_, changing_field)| changing_field == &field_name)
+            {
+                return;
+            }
 
-        if try_predicate_is_option(field_type) {
-            let inner_type = try_unwrap_option(field_type);
-            let builder_setter = quote! {
-                pub fn #field_name(mut self, #field_name: #inner_type) -> Self {
-                    self.#field_name = ::std::option::Option::Some(#field_name);
-                    self
-                }
-            };
+            if try_predicate_is_option(field_type) {
+                let inner_type = try_unwrap_option(field_type);
+                let builder_setter = quote! {
+                    pub fn #field_name(mut self, #field_name: #inner_type) -> Self {
+                        self.#field_name = ::std::option::Option::Some(#field_name);
+                        self
+                    }
+                };
+
+                builder_setters.push(builder_setter);
+            } else if try_predicate_is_vec(field_type) {
+                // `#[default(expr)]`: the field stays `None` until set, `build()` falls back to `expr`.
+                let target = if try_predicate_has_default(field) {
+                    quote! { self.#field_name.get_or_insert_with(::std::vec::Vec::new) }
+                } else {
+                    quote! { self.#field_name }
+                };
+                let builder_setter = quote! {
+                    pub fn #field_name(mut self, #field_name: #field_type) -> Self {
+                        #target.extend(#field_name);
+                        self
+                    }
+                };
+                builder_setters.push(builder_setter);
 
-            builder_setters.push(builder_setter);
-        } else if try_predicate_is_vec(field_type) {
-            let builder_setter = quote! {
-                pub fn #field_name(mut self, #field_name: #field_type) -> Self {
-                    self.#field_name.extend(#field_name);
-                    self
-                }
-            };
-            builder_setters.push(builder_setter);
-
-            // #[builder(method = "activity")]
-            // activities: Vec<String>
-            if let Ok(builder_method_opt) = try_extract_field_attribute_path_attribute(
-                BUILDER_ATTR_NAME,
-                BUILDER_ATTR_CUSTOM_METHOD,
-                field,
-            ) {
-                if let Some(builder_method) = builder_method_opt {
+                // #[builder(method = "activity")]
+                // activities: Vec<String>
+                if let Ok(Some(builder_method)) = try_parse_builder_method(field) {
                     let inner_type = try_unwrap_vec(field_type);
 
                     let builder_method_setter = quote! {
                         pub fn #builder_method(mut self, #builder_method: #inner_type) -> Self {
-                            self.#field_name.push(#builder_method);
+                            #target.push(#builder_method);
                             self
                         }
                     };
                     builder_setters.push(builder_method_setter);
                 }
-            }
-        } else {
-            let builder_setter = quote! {
-            pub fn #field_name(mut self, #field_name: #field_type) -> Self {
-                    self.#field_name = ::std::option::Option::Some(#field_name);
-                    self
-                }
-            };
+            } else {
+                let builder_setter = quote! {
+                pub fn #field_name(mut self, #field_name: #field_type) -> Self {
+                        self.#field_name = ::std::option::Option::Some(#field_name);
+                        self
+                    }
+                };
 
-            builder_setters.push(builder_setter);
-        }
-    });
+                builder_setters.push(builder_setter);
+            }
+        });
 
     builder_setters
 }
 
 pub fn derive_builder_defaults(derive_input: &DeriveInput) -> Vec<proc_macro2::TokenStream> {
     let mut builder_defaults = Vec::new();
-    let fields = try_parse_named_fields(&derive_input);
-    fields.iter().for_each(|field| {
-        let field_name = field.ident.clone().unwrap();
-        let field_type = &field.ty;
-
-        if try_predicate_is_vec(field_type) {
-            let builder_default = quote! {
-                #field_name: ::std::vec::Vec::new()
-            };
-            builder_defaults.push(builder_default);
-        } else {
-            let builder_default = quote! {
-                #field_name: ::std::option::Option::None
-            };
-            builder_defaults.push(builder_default);
-        }
-    });
+    let fields = try_parse_named_fields(derive_input);
+    fields
+        .iter()
+        .filter(|field| !try_predicate_is_hidden_field(field))
+        .for_each(|field| {
+            let field_name = field.ident.clone().unwrap();
+            let field_type = &field.ty;
+
+            if try_predicate_is_vec(field_type) && !try_predicate_has_default(field) {
+                let builder_default = quote! {
+                    #field_name: ::std::vec::Vec::new()
+                };
+                builder_defaults.push(builder_default);
+            } else {
+                let builder_default = quote! {
+                    #field_name: ::std::option::Option::None
+                };
+                builder_defaults.push(builder_default);
+            }
+        });
 
     builder_defaults
 }
 
 pub fn derive_build_field_checker(derive_input: &DeriveInput) -> Vec<proc_macro2::TokenStream> {
     let mut build_field_checkers = Vec::new();
-    let fields = try_parse_named_fields(&derive_input);
-    fields.iter().for_each(|field| {
-        let field_name = field.ident.clone().unwrap();
-        let field_type = &field.ty;
-
-        if try_predicate_is_not_option_and_vec(field_type) {
-            let field_checker = quote! {
-                if self.#field_name.is_none() {
-                    let err = format!("Missing field: `{}`!", stringify!(#field_name));
-                    return ::std::result::Result::Err(err.into())
-                }
-            };
-            build_field_checkers.push(field_checker);
-        }
-    });
+    let fields = try_parse_named_fields(derive_input);
+    fields
+        .iter()
+        .filter(|field| !try_predicate_is_hidden_field(field))
+        .for_each(|field| {
+            let field_name = field.ident.clone().unwrap();
+
+            if try_predicate_is_required_field(field).unwrap_or(true) {
+                let field_checker = quote! {
+                    if self.#field_name.is_none() {
+                        let err = format!("Missing field: `{}`!", stringify!(#field_name));
+                        return ::std::result::Result::Err(err.into())
+                    }
+                };
+                build_field_checkers.push(field_checker);
+            }
+        });
 
     build_field_checkers
 }
 
 pub fn derive_build_fields(derive_input: &DeriveInput) -> Vec<proc_macro2::TokenStream> {
     let mut build_fields = Vec::new();
-    let fields = try_parse_named_fields(&derive_input);
+    let fields = try_parse_named_fields(derive_input);
     fields.iter().for_each(|field| {
         let field_name = field.ident.clone().unwrap();
         let field_type = &field.ty;
 
-        if try_predicate_is_option(field_type) {
+        // #[track_changes]: __lombok_changes, ...
+        if try_predicate_is_hidden_field(field) {
             let build_field = quote! {
-                #field_name: self.#field_name.clone().unwrap()
+                #field_name: ::std::default::Default::default()
+            };
+            build_fields.push(build_field);
+        } else if let Ok(Some(default)) = try_derive_field_default(field) {
+            // #[builder(default)]
+            let build_field = if try_predicate_is_option(field_type) {
+                quote! {
+                    #field_name: self.#field_name.or_else(|| #default)
+                }
+            } else {
+                quote! {
+                    #field_name: self.#field_name.unwrap_or_else(|| #default)
+                }
             };
             build_fields.push(build_field);
-        } else if try_predicate_is_vec(field_type) {
+        } else if try_predicate_is_option(field_type) || try_predicate_is_vec(field_type) {
             let build_field = quote! {
-                #field_name: self.#field_name.clone()
+                #field_name: self.#field_name
             };
             build_fields.push(build_field);
         } else {
             let build_field = quote! {
-                #field_name: self.#field_name.clone().unwrap()
+                #field_name: self.#field_name.unwrap()
             };
             build_fields.push(build_field);
         }
@@ -196,30 +246,400 @@ pub fn derive_build_fields(derive_input: &DeriveInput) -> Vec<proc_macro2::Token
     build_fields
 }
 
+/// `Debug` for `XxxBuilder`, printing a `#[sensitive]` field as `Some(***)` or `None`, never its value.
+///
+/// The `Debug` bounds of the other fields are deferred to the call site, like `derive_clone_bound` of `Setter`:
+/// a builder of fields that aren't `Debug` still compiles.
+pub fn try_derive_builder_debug(
+    derive_input: &DeriveInput,
+    builder_name: &Ident,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut entries = Vec::new();
+    let mut bounds: Vec<WherePredicate> = Vec::new();
+
+    let fields = try_parse_named_fields(derive_input);
+    for field in fields
+        .iter()
+        .filter(|field| !try_predicate_is_hidden_field(field))
+    {
+        let field_name = field.ident.as_ref().unwrap();
+        let label = field_name.unraw().to_string();
+
+        if Sensitive::try_parse(field)?.is_some() {
+            let masked = if try_predicate_is_vec(&field.ty) && !try_predicate_has_default(field) {
+                quote! { ::std::option::Option::Some(::std::format_args!("***")) }
+            } else {
+                quote! { self.#field_name.as_ref().map(|_| ::std::format_args!("***")) }
+            };
+            entries.push(quote! {
+                .field(#label, &#masked)
+            });
+        } else {
+            let field_type = &field.ty;
+            entries.push(quote! {
+                .field(#label, &self.#field_name)
+            });
+            bounds.push(parse_quote!(for<'__lombok> #field_type: ::std::fmt::Debug));
+        }
+    }
+
+    let mut generics = derive_input.generics.clone();
+    generics.make_where_clause().predicates.extend(bounds);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let name = builder_name.to_string();
+    Ok(quote! {
+        impl #impl_generics ::std::fmt::Debug for #builder_name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.debug_struct(#name)
+                    #(
+                        #entries
+                    )*
+                    .finish()
+            }
+        }
+    })
+}
+
+/// Try to parse the element method of `#[builder(method = "activity")]`.
+pub(crate) fn try_parse_builder_method(field: &Field) -> syn::Result<Option<Ident>> {
+    let field_args = AttributeArgs::try_parse(BUILDER_ATTR_NAME, BUILDER_KEYS, &field.attrs)?;
+
+    match field_args.try_str(BUILDER_ATTR_CUSTOM_METHOD)? {
+        Some(builder_method) => Ok(Some(builder_method.parse()?)),
+        None => Ok(None),
+    }
+}
+
+/// Try to derive the default value of a field left unset.
+///
+/// - `#[builder(default)]` -> `Default::default()`
+/// - `#[builder(default = expr)]` -> `expr`
+/// - `#[default = literal]`, `#[default(expr)]`, unless `#[builder(default ..)]` says otherwise
+pub(crate) fn try_derive_field_default(
+    field: &Field,
+) -> syn::Result<Option<proc_macro2::TokenStream>> {
+    let field_args = AttributeArgs::try_parse(BUILDER_ATTR_NAME, BUILDER_KEYS, &field.attrs)?;
+
+    match field_args.get(BUILDER_ATTR_DEFAULT) {
+        None => Ok(try_parse_default(field)?.map(|default| default.to_token_stream())),
+        Some(AttributeArg { value: None, .. }) => {
+            Ok(Some(quote! { ::std::default::Default::default() }))
+        }
+        Some(AttributeArg {
+            value: Some(default),
+            ..
+        }) => Ok(Some(default.to_token_stream())),
+    }
+}
+
+fn try_predicate_has_default(field: &Field) -> bool {
+    matches!(try_derive_field_default(field), Ok(Some(_)))
+}
+
+/// Try to predicate that `build()` and `RequiredArgsConstructor` can't do without the field:
+/// it's neither an `Option`, a `Vec` nor a field with a default.
+pub(crate) fn try_predicate_is_required_field(field: &Field) -> syn::Result<bool> {
+    Ok(!try_predicate_is_option(&field.ty)
+        && !try_predicate_is_vec(&field.ty)
+        && try_derive_field_default(field)?.is_none())
+}
+
+/// Try to find the type parameters chosen to be switched by the builder, either on the field
+/// or by name on the struct:
+///
+/// ```ignore
+/// #[derive(Builder)]
+/// pub struct Request<B> {
+///     url: String,
+///     #[builder(type_changing)]
+///     body: B,
+/// }
+///
+/// #[derive(Builder)]
+/// #[builder(type_changing = "B")]
+/// pub struct Request<B> {
+///     url: String,
+///     body: B,
+/// }
+/// ```
+///
+/// A chosen type parameter `B` must be the bare type of exactly one field, no other field may mention it,
+/// and it can have neither bounds nor `where` predicates, since `RequestBuilder<()>` would have to satisfy them.
+/// Every other type parameter, bounded or not, is left alone.
+///
+/// Returns the `(type parameter, field)` pairs.
+pub fn try_find_type_changing_params(
+    derive_input: &DeriveInput,
+) -> syn::Result<Vec<(Ident, Ident)>> {
+    let fields = try_parse_named_fields(derive_input);
+    let struct_args =
+        AttributeArgs::try_parse(BUILDER_ATTR_NAME, BUILDER_KEYS, &derive_input.attrs)?;
+
+    let mut chosen: Vec<(Ident, proc_macro2::TokenStream)> = Vec::new();
+    if let Some(arg) = struct_args.get(BUILDER_ATTR_TYPE_CHANGING) {
+        let Some(names) = struct_args
+            .try_str(BUILDER_ATTR_TYPE_CHANGING)
+            .ok()
+            .flatten()
+        else {
+            return Err(syn::Error::new_spanned(
+                &arg.key,
+                r#"name the type parameters to switch, e.g. `#[builder(type_changing = "B")]`, or put `#[builder(type_changing)]` on the field"#,
+            ));
+        };
+        for name in names.value().split(',').map(str::trim) {
+            let Some(param) = derive_input
+                .generics
+                .type_params()
+                .find(|param| param.ident == name)
+            else {
+                return Err(syn::Error::new_spanned(
+                    &names,
+                    format!(
+                        "`{}` isn't a type parameter of `{}`",
+                        name, derive_input.ident
+                    ),
+                ));
+            };
+            chosen.push((param.ident.clone(), names.to_token_stream()));
+        }
+    }
+    for field in fields.iter() {
+        let field_args = AttributeArgs::try_parse(BUILDER_ATTR_NAME, BUILDER_KEYS, &field.attrs)?;
+        let Some(arg) = field_args.get(BUILDER_ATTR_TYPE_CHANGING) else {
+            continue;
+        };
+        let Some(flag) = field_args
+            .try_flag(BUILDER_ATTR_TYPE_CHANGING)
+            .ok()
+            .flatten()
+        else {
+            return Err(syn::Error::new_spanned(
+                &arg.key,
+                "expected `type_changing` or `type_changing = true|false` on a field",
+            ));
+        };
+        if !flag {
+            continue;
+        }
+        let Some(param) = derive_input
+            .generics
+            .type_params()
+            .find(|param| try_predicate_is_bare_ident(&field.ty, &param.ident))
+        else {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                "`#[builder(type_changing)]` expects a field declared as a bare type parameter",
+            ));
+        };
+        chosen.push((param.ident.clone(), arg.key.to_token_stream()));
+    }
+
+    let where_predicates: Vec<&WherePredicate> = derive_input
+        .generics
+        .where_clause
+        .as_ref()
+        .map(|where_clause| where_clause.predicates.iter().collect())
+        .unwrap_or_default();
+
+    let mut type_changing_params: Vec<(Ident, Ident)> = Vec::new();
+    for (ident, span) in chosen {
+        if type_changing_params
+            .iter()
+            .any(|(param, _)| param == &ident)
+        {
+            continue;
+        }
+
+        let mut owners = fields
+            .iter()
+            .filter(|field| try_predicate_tokens_contain_ident(field.ty.to_token_stream(), &ident));
+        let field = match (owners.next(), owners.next()) {
+            (Some(field), None) if try_predicate_is_bare_ident(&field.ty, &ident) => field,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    span,
+                    format!(
+                        "`#[builder(type_changing)]` can't switch `{}`, it must be the bare type of a single field and appear in no other field",
+                        ident
+                    ),
+                ))
+            }
+        };
+
+        let param = derive_input
+            .generics
+            .type_params()
+            .find(|param| param.ident == ident)
+            .unwrap();
+        if !param.bounds.is_empty() {
+            return Err(try_bounded_param_error(param));
+        }
+        if let Some(predicate) = where_predicates.iter().find(|predicate| {
+            try_predicate_tokens_contain_ident(predicate.to_token_stream(), &ident)
+        }) {
+            return Err(try_bounded_param_error(predicate));
+        }
+
+        type_changing_params.push((ident, field.ident.clone().unwrap()));
+    }
+
+    Ok(type_changing_params)
+}
+
+fn try_bounded_param_error(bounded: impl ToTokens) -> syn::Error {
+    syn::Error::new_spanned(
+        bounded,
+        "`#[builder(type_changing)]` can't switch a bounded type parameter, move the bound to the `impl` blocks using it",
+    )
+}
+
+fn try_predicate_is_bare_ident(ty: &Type, ident: &Ident) -> bool {
+    match ty {
+        Type::Path(type_path) => type_path.qself.is_none() && type_path.path.is_ident(ident),
+        _ => false,
+    }
+}
+
+fn try_predicate_tokens_contain_ident(tokens: proc_macro2::TokenStream, ident: &Ident) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ref target) => target == ident,
+        TokenTree::Group(group) => try_predicate_tokens_contain_ident(group.stream(), ident),
+        _ => false,
+    })
+}
+
+/// Build the generic arguments `<'a, T, N>` of `generics`, replacing the type parameters found in `replacements`.
+///
+/// - `Request<'a, T, B, N>` + `B -> ()` -> `<'a, T, (), N>`
+pub fn derive_generic_arguments(
+    generics: &Generics,
+    replacements: &[(&Ident, proc_macro2::TokenStream)],
+) -> proc_macro2::TokenStream {
+    let arguments: Vec<proc_macro2::TokenStream> = generics
+        .params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Lifetime(lifetime) => lifetime.lifetime.to_token_stream(),
+            GenericParam::Type(ty) => replacements
+                .iter()
+                .find(|(ident, _)| *ident == &ty.ident)
+                .map(|(_, replacement)| replacement.clone())
+                .unwrap_or_else(|| ty.ident.to_token_stream()),
+            GenericParam::Const(constant) => constant.ident.to_token_stream(),
+        })
+        .collect();
+
+    if arguments.is_empty() {
+        return proc_macro2::TokenStream::new();
+    }
+
+    quote! { <#(#arguments),*> }
+}
+
+/// `#[builder(type_changing)]`: `.body(json)` switches the `B` of `RequestBuilder<B>` to the type of `json`.
+pub fn derive_builder_type_changing_setters(
+    derive_input: &DeriveInput,
+    builder_name: &Ident,
+    type_changing_params: &[(Ident, Ident)],
+) -> Vec<proc_macro2::TokenStream> {
+    let fields = try_parse_named_fields(derive_input);
+
+    type_changing_params
+        .iter()
+        .map(|(param, changing_field)| {
+            let target_param = format_ident!("__{}", param);
+            let target_arguments = derive_generic_arguments(
+                &derive_input.generics,
+                &[(param, target_param.to_token_stream())],
+            );
+
+            let moved_fields = fields
+                .iter()
+                .filter(|field| !try_predicate_is_hidden_field(field))
+                .map(|field| field.ident.clone().unwrap())
+                .filter(|field_name| field_name != changing_field)
+                .map(|field_name| quote! { #field_name: self.#field_name });
+
+            quote! {
+                pub fn #changing_field<#target_param>(self, #changing_field: #target_param) -> #builder_name #target_arguments {
+                    #builder_name {
+                        #(
+                            #moved_fields,
+                        )*
+                        #changing_field: ::std::option::Option::Some(#changing_field)
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
 pub fn derive_builder(input: TokenStream) -> TokenStream {
     let derive_input = try_derive_input(input);
     let struct_name = &derive_input.ident;
 
-    let (impl_generics, ty_generics, where_clause) = &derive_input.generics.split_for_impl();
+    let generics = &derive_input.generics;
+    let (impl_generics, ty_generics, where_clause) = &generics.split_for_impl();
     let visibility = derive_input.vis.clone();
 
     let builder_name = format_ident!("{}{}", struct_name, BUILDER_SUFFIX);
 
+    for field in try_parse_named_fields(&derive_input).iter() {
+        if let Err(err) = try_parse_builder_method(field).and(try_derive_field_default(field)) {
+            return err.to_compile_error().into();
+        }
+    }
+
+    let type_changing_params = match try_find_type_changing_params(&derive_input) {
+        Ok(type_changing_params) => type_changing_params,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    // `Request::builder()` starts every type-changing parameter out as `()`.
+    let mut builder_generics = generics.clone();
+    builder_generics.params = generics
+        .params
+        .iter()
+        .filter(|param| match param {
+            GenericParam::Type(ty) => !type_changing_params
+                .iter()
+                .any(|(changing_param, _)| changing_param == &ty.ident),
+            _ => true,
+        })
+        .cloned()
+        .collect();
+    let (builder_impl_generics, _, _) = &builder_generics.split_for_impl();
+    let unit_replacements: Vec<(&Ident, proc_macro2::TokenStream)> = type_changing_params
+        .iter()
+        .map(|(param, _)| (param, quote! { () }))
+        .collect();
+    let struct_arguments = derive_generic_arguments(generics, &unit_replacements);
+
     let builder_fields = derive_builder_fields(&derive_input);
-    let builder_setters = derive_builder_setters(&derive_input);
+    let builder_setters = derive_builder_setters(&derive_input, &type_changing_params);
+    let builder_type_changing_setters =
+        derive_builder_type_changing_setters(&derive_input, &builder_name, &type_changing_params);
     let builder_defaults = derive_builder_defaults(&derive_input);
     let build_field_checkers = derive_build_field_checker(&derive_input);
     let build_fields = derive_build_fields(&derive_input);
+    let builder_debug = match try_derive_builder_debug(&derive_input, &builder_name) {
+        Ok(builder_debug) => builder_debug,
+        Err(err) => return err.to_compile_error().into(),
+    };
 
     let expanded = quote! {
-        #visibility struct #builder_name #ty_generics #where_clause {
+        #visibility struct #builder_name #generics #where_clause {
             #(
                 #builder_fields
             ),*
         }
 
-        impl #impl_generics #struct_name #ty_generics #where_clause {
-            pub fn builder() -> #builder_name #ty_generics {
+        #builder_debug
+
+        impl #builder_impl_generics #struct_name #struct_arguments #where_clause {
+            pub fn builder() -> #builder_name #struct_arguments {
                 #builder_name {
                     #(
                         #builder_defaults
@@ -233,6 +653,10 @@ pub fn derive_builder(input: TokenStream) -> TokenStream {
                 #builder_setters
             )*
 
+            #(
+                #builder_type_changing_setters
+            )*
+
             pub fn build(self) -> ::std::result::Result<#struct_name #ty_generics, ::std::boxed::Box<dyn std::error::Error + 'static>> {
                 #(
                     #build_field_checkers
@@ -0,0 +1,265 @@
+/*
+ * Copyright © 2024 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// constructor
+
+// ----------------------------------------------------------------
+
+use proc_macro::TokenStream;
+
+use quote::{format_ident, quote};
+use syn::{parse_quote, DeriveInput, Field, GenericParam, Ident};
+use synext::{try_derive_input, try_parse_named_fields};
+
+use crate::attribute::{
+    try_predicate_is_hidden_field, ArgShape, AttributeArgs, AttributeKey, VIS_KEYS,
+};
+use crate::builder::{try_derive_field_default, try_predicate_is_required_field};
+
+// ----------------------------------------------------------------
+
+const CONSTRUCTOR_DEFAULT_NAME: &str = "new";
+
+const NO_ARGS_ATTR_NAME: &str = "no_args";
+const ALL_ARGS_ATTR_NAME: &str = "all_args";
+const REQUIRED_ARGS_ATTR_NAME: &str = "required_args";
+const CONSTRUCTOR_ATTR_NAME: &str = "name";
+
+const NEW_ATTR_NAME: &str = "new";
+const NEW_ATTR_INTO: &str = "into";
+
+/// `#[no_args(..)]`, `#[all_args(..)]` and `#[required_args(..)]`.
+const CONSTRUCTOR_KEYS: &[&[AttributeKey]] = &[VIS_KEYS, &[(CONSTRUCTOR_ATTR_NAME, ArgShape::Str)]];
+const NEW_KEYS: &[&[AttributeKey]] = &[&[(NEW_ATTR_INTO, ArgShape::Flag)]];
+
+// ----------------------------------------------------------------
+
+pub(crate) fn derive_no_args_constructor(input: TokenStream) -> TokenStream {
+    let derive_input = try_derive_input(input);
+
+    match try_derive_no_args_constructor(&derive_input) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+fn try_derive_no_args_constructor(
+    derive_input: &DeriveInput,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let struct_args =
+        AttributeArgs::try_parse(NO_ARGS_ATTR_NAME, CONSTRUCTOR_KEYS, &derive_input.attrs)?;
+
+    let mut inits = Vec::new();
+
+    let fields = try_parse_named_fields(derive_input);
+    for field in fields.iter() {
+        let field_name = field.ident.as_ref().unwrap();
+
+        // #[default(expr)], #[builder(default = expr)]
+        let default = if try_predicate_is_hidden_field(field) {
+            None
+        } else {
+            try_derive_field_default(field)?
+        };
+        let default = default.unwrap_or_else(|| quote! { ::std::default::Default::default() });
+        inits.push(quote! { #field_name: #default });
+    }
+
+    // T: Default, like #[derive(Default)]
+    let mut derive_input = derive_input.clone();
+    let type_params: Vec<Ident> = derive_input
+        .generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(type_param) => Some(type_param.ident.clone()),
+            _ => None,
+        })
+        .collect();
+    let where_clause = derive_input.generics.make_where_clause();
+    for type_param in type_params {
+        where_clause
+            .predicates
+            .push(parse_quote!(#type_param: ::std::default::Default));
+    }
+
+    let struct_name = &derive_input.ident;
+    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+    let constructor =
+        try_derive_constructor(&derive_input, &struct_args, Vec::new(), inits.clone())?;
+
+    let expanded = quote! {
+        impl #impl_generics ::std::default::Default for #struct_name #ty_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    #(
+                        #inits,
+                    )*
+                }
+            }
+        }
+
+        #constructor
+    };
+
+    Ok(expanded)
+}
+
+pub(crate) fn derive_all_args_constructor(input: TokenStream) -> TokenStream {
+    let derive_input = try_derive_input(input);
+
+    match try_derive_all_args_constructor(&derive_input) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+fn try_derive_all_args_constructor(
+    derive_input: &DeriveInput,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let struct_args =
+        AttributeArgs::try_parse(ALL_ARGS_ATTR_NAME, CONSTRUCTOR_KEYS, &derive_input.attrs)?;
+    let new_args = AttributeArgs::try_parse(NEW_ATTR_NAME, NEW_KEYS, &derive_input.attrs)?;
+
+    let mut arguments = Vec::new();
+    let mut inits = Vec::new();
+
+    let fields = try_parse_named_fields(derive_input);
+    for field in fields.iter() {
+        let field_name = field.ident.as_ref().unwrap();
+
+        // #[track_changes]: __lombok_changes, ...
+        if try_predicate_is_hidden_field(field) {
+            inits.push(quote! { #field_name: ::std::default::Default::default() });
+            continue;
+        }
+
+        let field_args = AttributeArgs::try_parse(NEW_ATTR_NAME, NEW_KEYS, &field.attrs)?;
+        let (argument, init) = try_derive_argument(field, &field_args, &new_args)?;
+        arguments.push(argument);
+        inits.push(init);
+    }
+
+    try_derive_constructor(derive_input, &struct_args, arguments, inits)
+}
+
+pub(crate) fn derive_required_args_constructor(input: TokenStream) -> TokenStream {
+    let derive_input = try_derive_input(input);
+
+    match try_derive_required_args_constructor(&derive_input) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+fn try_derive_required_args_constructor(
+    derive_input: &DeriveInput,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let struct_args = AttributeArgs::try_parse(
+        REQUIRED_ARGS_ATTR_NAME,
+        CONSTRUCTOR_KEYS,
+        &derive_input.attrs,
+    )?;
+    let new_args = AttributeArgs::try_parse(NEW_ATTR_NAME, NEW_KEYS, &derive_input.attrs)?;
+
+    let mut arguments = Vec::new();
+    let mut inits = Vec::new();
+
+    let fields = try_parse_named_fields(derive_input);
+    for field in fields.iter() {
+        let field_name = field.ident.as_ref().unwrap();
+
+        // #[track_changes]: __lombok_changes, ...
+        if try_predicate_is_hidden_field(field) {
+            inits.push(quote! { #field_name: ::std::default::Default::default() });
+            continue;
+        }
+
+        // Option<T>, Vec<T>, #[builder(default)], ...
+        if !try_predicate_is_required_field(field)? {
+            let default = try_derive_field_default(field)?
+                .unwrap_or_else(|| quote! { ::std::default::Default::default() });
+            inits.push(quote! { #field_name: #default });
+            continue;
+        }
+
+        let field_args = AttributeArgs::try_parse(NEW_ATTR_NAME, NEW_KEYS, &field.attrs)?;
+        let (argument, init) = try_derive_argument(field, &field_args, &new_args)?;
+        arguments.push(argument);
+        inits.push(init);
+    }
+
+    try_derive_constructor(derive_input, &struct_args, arguments, inits)
+}
+
+// ----------------------------------------------------------------
+
+/// The argument and the field initializer of a constructor argument.
+///
+/// - `id: u32` -> `id: u32`, `id`
+/// - `#[new(into)] name: String` -> `name: impl Into<String>`, `name: name.into()`
+fn try_derive_argument(
+    field: &Field,
+    field_args: &AttributeArgs,
+    new_args: &AttributeArgs,
+) -> syn::Result<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
+    let field_name = field.ident.as_ref().unwrap();
+    let field_type = &field.ty;
+
+    if field_args.try_flag_or(new_args, NEW_ATTR_INTO)? {
+        Ok((
+            quote! { #field_name: impl ::std::convert::Into<#field_type> },
+            quote! { #field_name: #field_name.into() },
+        ))
+    } else {
+        Ok((quote! { #field_name: #field_type }, quote! { #field_name }))
+    }
+}
+
+/// `#vis fn #name(#arguments) -> Self`
+///
+/// - `#[x(name = "create")]` renames it, `new` by default.
+/// - `#[x(vis = "pub(crate)")]` overrides the visibility of the struct.
+fn try_derive_constructor(
+    derive_input: &DeriveInput,
+    struct_args: &AttributeArgs,
+    arguments: Vec<proc_macro2::TokenStream>,
+    inits: Vec<proc_macro2::TokenStream>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let struct_name = &derive_input.ident;
+    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+
+    let vis = struct_args.try_vis_or(&AttributeArgs::default(), &derive_input.vis)?;
+    let name: Ident = match struct_args.try_str(CONSTRUCTOR_ATTR_NAME)? {
+        Some(name) => name.parse()?,
+        None => format_ident!("{}", CONSTRUCTOR_DEFAULT_NAME),
+    };
+
+    let expanded = quote! {
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            #[allow(clippy::too_many_arguments)]
+            #vis fn #name(#(#arguments),*) -> Self {
+                Self {
+                    #(
+                        #inits,
+                    )*
+                }
+            }
+        }
+    };
+
+    Ok(expanded)
+}
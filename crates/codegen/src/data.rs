@@ -0,0 +1,72 @@
+/*
+ * Copyright © 2024 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// data
+
+// ----------------------------------------------------------------
+
+use proc_macro::TokenStream;
+
+use syn::DeriveInput;
+use synext::try_derive_input;
+
+use crate::attribute::{ArgShape, AttributeArgs, AttributeKey};
+use crate::builder::derive_builder;
+use crate::getter::derive_getter;
+use crate::setter::derive_setter;
+
+// ----------------------------------------------------------------
+
+pub(crate) const DATA_ATTR_NAME: &str = "data";
+pub(crate) const DATA_ATTR_SETTER: &str = "setter";
+
+const DATA_ATTR_GETTER: &str = "getter";
+const DATA_ATTR_BUILDER: &str = "builder";
+
+pub(crate) const DATA_KEYS: &[&[AttributeKey]] = &[&[
+    (DATA_ATTR_SETTER, ArgShape::Flag),
+    (DATA_ATTR_GETTER, ArgShape::Flag),
+    (DATA_ATTR_BUILDER, ArgShape::Flag),
+]];
+
+// ----------------------------------------------------------------
+
+pub(crate) fn derive_data(input: TokenStream) -> TokenStream {
+    let derive_input = try_derive_input(input.clone());
+
+    match try_derive_data(&derive_input, input) {
+        Ok(expanded) => expanded,
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+/// `#[data(getter = false, setter = false, builder = false)]` turns the parts off, every part is on by default.
+fn try_derive_data(derive_input: &DeriveInput, input: TokenStream) -> syn::Result<TokenStream> {
+    let data_args = AttributeArgs::try_parse(DATA_ATTR_NAME, DATA_KEYS, &derive_input.attrs)?;
+
+    let mut expanded = Vec::new();
+    if data_args.try_flag(DATA_ATTR_SETTER)?.unwrap_or(true) {
+        expanded.push(derive_setter(input.clone()));
+    }
+    if data_args.try_flag(DATA_ATTR_GETTER)?.unwrap_or(true) {
+        expanded.push(derive_getter(input.clone()));
+    }
+    if data_args.try_flag(DATA_ATTR_BUILDER)?.unwrap_or(true) {
+        expanded.push(derive_builder(input));
+    }
+
+    Ok(TokenStream::from_iter(expanded))
+}
@@ -0,0 +1,130 @@
+/*
+ * Copyright © 2024 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// eq
+
+// ----------------------------------------------------------------
+
+use proc_macro::TokenStream;
+
+use quote::quote;
+use syn::{DeriveInput, Field};
+use synext::{try_derive_input, try_parse_named_fields};
+
+use crate::attribute::{try_select_fields, ArgShape, AttributeArgs, AttributeKey, SELECTION_KEYS};
+
+// ----------------------------------------------------------------
+
+const EQ_ATTR_NAME: &str = "eq";
+const EQ_ATTR_ID: &str = "id";
+
+const EQ_KEYS: &[&[AttributeKey]] = &[SELECTION_KEYS, &[(EQ_ATTR_ID, ArgShape::Flag)]];
+
+// ----------------------------------------------------------------
+
+pub(crate) fn derive_equals_and_hash_code(input: TokenStream) -> TokenStream {
+    let derive_input = try_derive_input(input);
+
+    match try_derive_equals_and_hash_code(&derive_input) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+pub(crate) fn try_derive_equals_and_hash_code(
+    derive_input: &DeriveInput,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let struct_name = &derive_input.ident;
+    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+
+    let fields = try_parse_named_fields(derive_input);
+    let selected_fields = try_select_eq_fields(fields)?;
+
+    let field_names: Vec<_> = selected_fields
+        .iter()
+        .map(|field| field.ident.as_ref().unwrap())
+        .collect();
+    let field_types: Vec<_> = selected_fields.iter().map(|field| &field.ty).collect();
+
+    // The higher-ranked bounds defer `T: Eq`, `T: Hash`, ... to the use site:
+    // a struct with an `f64` field gets `PartialEq` only.
+    let predicates = where_clause
+        .map(|where_clause| where_clause.predicates.iter().collect())
+        .unwrap_or_else(Vec::new);
+
+    let expanded = quote! {
+        impl #impl_generics ::std::cmp::PartialEq for #struct_name #ty_generics
+        where
+            #(#predicates,)*
+            #(for<'__lombok> #field_types: ::std::cmp::PartialEq,)*
+        {
+            fn eq(&self, other: &Self) -> bool {
+                true #(&& self.#field_names == other.#field_names)*
+            }
+        }
+
+        impl #impl_generics ::std::cmp::Eq for #struct_name #ty_generics
+        where
+            #(#predicates,)*
+            #(for<'__lombok> #field_types: ::std::cmp::Eq,)*
+        {
+        }
+
+        impl #impl_generics ::std::hash::Hash for #struct_name #ty_generics
+        where
+            #(#predicates,)*
+            #(for<'__lombok> #field_types: ::std::hash::Hash,)*
+        {
+            fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+                #(
+                    ::std::hash::Hash::hash(&self.#field_names, state);
+                )*
+            }
+        }
+    };
+
+    Ok(expanded)
+}
+
+/// Try to select the fields `eq()` and `hash()` cover.
+///
+/// - `#[eq(id)]` on any field selects the `#[eq(id)]` fields only, the entity mode.
+/// - Otherwise `#[eq(skip)]` and `#[eq(only)]` select the fields like their `Getter` counterparts.
+fn try_select_eq_fields<'a>(
+    fields: impl IntoIterator<Item = &'a Field>,
+) -> syn::Result<Vec<&'a Field>> {
+    let selected_fields = try_select_fields(EQ_ATTR_NAME, EQ_KEYS, fields)?;
+
+    let mut id_fields = Vec::new();
+    for (field, field_args) in selected_fields.iter() {
+        if try_predicate_is_id_field(field_args)? {
+            id_fields.push(*field);
+        }
+    }
+
+    if !id_fields.is_empty() {
+        return Ok(id_fields);
+    }
+
+    Ok(selected_fields
+        .into_iter()
+        .map(|(field, _)| field)
+        .collect())
+}
+
+fn try_predicate_is_id_field(field_args: &AttributeArgs) -> syn::Result<bool> {
+    Ok(field_args.try_flag(EQ_ATTR_ID)?.unwrap_or_default())
+}
@@ -21,61 +21,285 @@
 use proc_macro::TokenStream;
 
 use quote::{format_ident, quote, ToTokens};
-use syn::Type;
-use synext::{try_derive_input, try_parse_named_fields};
+use syn::{DeriveInput, LitStr, Type};
+use synext::{
+    try_derive_input, try_parse_named_fields, try_predicate_is_option, try_unwrap_option,
+};
+
+use crate::attribute::{
+    try_select_fields, ArgShape, AttributeArgs, AttributeKey, SELECTION_KEYS, VIS_KEYS,
+};
+use crate::interior::{Interior, InteriorKind, INTERIOR_KEYS};
+use crate::types::{try_extract_type_arguments, try_predicate_is_any_type};
 
 // ----------------------------------------------------------------
 
 const GETTER_PREFIX: &str = "get_";
+const PREDICATE_GETTER_PREFIX: &str = "is_";
+const OPTION_GETTER_PREFIX: &str = "has_";
+const OPTION_GETTER_OR_SUFFIX: &str = "_or";
+const MUT_GETTER_SUFFIX: &str = "_mut";
+
+pub(crate) const GETTER_ATTR_NAME: &str = "getter";
+const GETTER_ATTR_DEREF: &str = "deref";
+const GETTER_ATTR_COPY: &str = "copy";
+const GETTER_ATTR_CONST: &str = "const";
+pub(crate) const GETTER_ATTR_MUT: &str = "mut";
+const GETTER_ATTR_STYLE: &str = "style";
+const GETTER_ATTR_LAZY: &str = "lazy";
+const GETTER_ATTR_PREDICATE: &str = "predicate";
+
+/// Every key of `#[getter(..)]`.
+pub(crate) const GETTER_KEYS: &[&[AttributeKey]] = &[
+    SELECTION_KEYS,
+    VIS_KEYS,
+    INTERIOR_KEYS,
+    &[
+        (GETTER_ATTR_DEREF, ArgShape::Flag),
+        (GETTER_ATTR_COPY, ArgShape::Flag),
+        (GETTER_ATTR_CONST, ArgShape::Flag),
+        (GETTER_ATTR_MUT, ArgShape::Flag),
+        (GETTER_ATTR_STYLE, ArgShape::Str),
+        (GETTER_ATTR_LAZY, ArgShape::Expr),
+        (GETTER_ATTR_PREDICATE, ArgShape::Flag),
+    ],
+];
+
+/// Primitive scalar types whose getters return by value unless `#[getter(copy = false)]`.
+const SCALAR_TYPES: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize", "f32",
+    "f64", "bool", "char",
+];
+
+// ----------------------------------------------------------------
+
+/// The signature and body shared by `get_x()` and `x()`.
+struct GetterBody {
+    return_type: proc_macro2::TokenStream,
+    body: proc_macro2::TokenStream,
+    /// Whether `body` can be evaluated in a `const fn`.
+    constant: bool,
+}
+
+/// `#[getter(style = "prefixed" | "fluent" | "both")]`
+#[derive(Clone, Copy, PartialEq)]
+enum GetterStyle {
+    /// get_x()
+    Prefixed,
+    /// x()
+    Fluent,
+    /// get_x() and x()
+    Both,
+}
+
+impl GetterStyle {
+    fn try_parse(style: Option<LitStr>) -> syn::Result<Self> {
+        let Some(style) = style else {
+            return Ok(GetterStyle::Both);
+        };
+
+        match style.value().as_str() {
+            "prefixed" => Ok(GetterStyle::Prefixed),
+            "fluent" => Ok(GetterStyle::Fluent),
+            "both" => Ok(GetterStyle::Both),
+            _ => Err(syn::Error::new_spanned(
+                style,
+                r#"expected `style = "prefixed" | "fluent" | "both"`"#,
+            )),
+        }
+    }
+
+    fn prefixed(self) -> bool {
+        self != GetterStyle::Fluent
+    }
+
+    fn fluent(self) -> bool {
+        self != GetterStyle::Prefixed
+    }
+}
 
 // ----------------------------------------------------------------
 
 pub(crate) fn derive_getter(input: TokenStream) -> TokenStream {
     let derive_input = try_derive_input(input);
+
+    match try_derive_getters(&derive_input) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+fn try_derive_getters(derive_input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let struct_name = &derive_input.ident;
 
     let (impl_generics, ty_generics, where_clause) = &derive_input.generics.split_for_impl();
 
+    let struct_args = AttributeArgs::try_parse(GETTER_ATTR_NAME, GETTER_KEYS, &derive_input.attrs)?;
+
     // Getters
     let mut getters = Vec::new();
 
-    let fields = try_parse_named_fields(&derive_input);
-    fields.iter().for_each(|field| {
+    let fields = try_parse_named_fields(derive_input);
+    for (field, field_args) in try_select_fields(GETTER_ATTR_NAME, GETTER_KEYS, fields)? {
         let field_name = field.ident.clone().unwrap();
         let field_type = &field.ty;
 
-        let return_type = match field_type {
-            Type::Reference(ref_type) => ref_type.to_token_stream(),
-            _ => quote! { &#field_type },
+        let style =
+            GetterStyle::try_parse(field_args.try_str_or(&struct_args, GETTER_ATTR_STYLE)?)?;
+        let vis = field_args.try_vis_or(&struct_args, &field.vis)?;
+        let deref = field_args.try_flag_or(&struct_args, GETTER_ATTR_DEREF)?;
+        // `Cell<T>`, `Mutex<T>`, `AtomicBool`, ...: read `T` through the wrapper.
+        let interior = Interior::try_parse(&field_args, &struct_args, field_type)?;
+        let value_type = interior
+            .as_ref()
+            .map_or(field_type, |interior| &interior.value_type);
+        // is_x(): bool fields and #[getter(predicate)], returned by value.
+        let predicate = field_args.try_flag_or_else(
+            &struct_args,
+            GETTER_ATTR_PREDICATE,
+            try_predicate_is_any_type(&["bool"], 0, value_type),
+        )?;
+        let copy = predicate
+            || field_args.try_flag_or_else(
+                &struct_args,
+                GETTER_ATTR_COPY,
+                try_predicate_is_any_type(SCALAR_TYPES, 0, value_type),
+            )?;
+        let constant = field_args.try_flag_or(&struct_args, GETTER_ATTR_CONST)?;
+        let mutable = field_args.try_flag_or(&struct_args, GETTER_ATTR_MUT)?;
+
+        let lazy = field_args.try_expr(GETTER_ATTR_LAZY)?;
+
+        let GetterBody {
+            return_type,
+            body: getter_body,
+            constant: body_is_constant,
+        } = match (lazy, field_type) {
+            (Some(init_fn), _) => try_derive_lazy_getter(&field_name, field_type, init_fn)?,
+            (None, _) if interior.is_some() => {
+                match derive_interior_getter(&field_name, interior.as_ref().unwrap(), copy) {
+                    Some(getter_body) => getter_body,
+                    None => GetterBody {
+                        return_type: quote! { &#field_type },
+                        body: quote! { &self.#field_name },
+                        constant: true,
+                    },
+                }
+            }
+            (None, Type::Reference(ref_type)) => GetterBody {
+                return_type: ref_type.to_token_stream(),
+                body: quote! { &self.#field_name },
+                constant: true,
+            },
+            (None, _) if copy => GetterBody {
+                return_type: field_type.to_token_stream(),
+                body: quote! { self.#field_name },
+                constant: true,
+            },
+            (None, _) if try_predicate_is_option(field_type) => {
+                derive_option_getter(&field_name, field_type, deref)
+            }
+            (None, _) if deref => derive_deref_getter(&field_name, field_type),
+            (None, _) => GetterBody {
+                return_type: quote! { &#field_type },
+                body: quote! { &self.#field_name },
+                constant: true,
+            },
+        };
+
+        // #[getter(const)]: only where the body allows it.
+        let constness = if constant && body_is_constant {
+            quote! { const }
+        } else {
+            quote! {}
         };
 
         // get_x()
         // |- get_id()
         // |- get_name()
         // |- ...
-        let getter_name = format_ident!("{}{}", GETTER_PREFIX, field_name);
-
-        let getter = quote! {
-            pub fn #getter_name(&self) -> #return_type {
-                &self.#field_name
-            }
+        // is_x()
+        // |- is_active()
+        // |- ...
+        let getter_name = if !predicate {
+            format_ident!("{}{}", GETTER_PREFIX, field_name)
+        } else if field_name.to_string().starts_with(PREDICATE_GETTER_PREFIX) {
+            field_name.clone()
+        } else {
+            format_ident!("{}{}", PREDICATE_GETTER_PREFIX, field_name)
         };
-        getters.push(getter);
+        let fluent_getter_name = format_ident!("{}", field_name);
+
+        if style.prefixed() {
+            let getter = quote! {
+                #vis #constness fn #getter_name(&self) -> #return_type {
+                    #getter_body
+                }
+            };
+            getters.push(getter);
+        }
 
         // x()
         // |- id()
         // |- name()
         // |- ...
-        let fluent_getter_name = format_ident!("{}", field_name);
-        let fluent_getter = quote! {
-            pub fn #fluent_getter_name(&self) -> #return_type {
-                &self.#field_name
+        // `is_active: bool` is its own is_x().
+        if style.fluent() && !(style.prefixed() && fluent_getter_name == getter_name) {
+            let fluent_getter = quote! {
+                #vis #constness fn #fluent_getter_name(&self) -> #return_type {
+                    #getter_body
+                }
+            };
+            getters.push(fluent_getter);
+        }
+
+        if lazy.is_none() && try_predicate_is_option(field_type) {
+            getters.extend(derive_option_getters(
+                &vis,
+                constant,
+                &field_name,
+                field_type,
+                copy,
+                deref,
+            ));
+        }
+
+        if mutable {
+            let (mut_return_type, mut_getter_body) =
+                derive_mut_getter(&field_name, field_type, deref);
+
+            // get_x_mut()
+            // |- get_hobby_mut()
+            // |- ...
+            if style.prefixed() {
+                let mut_getter_name =
+                    format_ident!("{}{}{}", GETTER_PREFIX, field_name, MUT_GETTER_SUFFIX);
+                let mut_getter = quote! {
+                    #vis fn #mut_getter_name(&mut self) -> #mut_return_type {
+                        #mut_getter_body
+                    }
+                };
+                getters.push(mut_getter);
             }
-        };
-        getters.push(fluent_getter);
-    });
 
+            // x_mut()
+            // |- hobby_mut()
+            // |- ...
+            if style.fluent() {
+                let fluent_mut_getter_name = format_ident!("{}{}", field_name, MUT_GETTER_SUFFIX);
+                let fluent_mut_getter = quote! {
+                    #vis fn #fluent_mut_getter_name(&mut self) -> #mut_return_type {
+                        #mut_getter_body
+                    }
+                };
+                getters.push(fluent_mut_getter);
+            }
+        }
+    }
+
+    // A `len` field gets a `len()` getter, without the `is_empty()` clippy asks for.
     let expanded = quote! {
+        #[allow(clippy::len_without_is_empty)]
         impl #impl_generics #struct_name #ty_generics #where_clause {
             #(
                 #getters
@@ -83,5 +307,260 @@ pub(crate) fn derive_getter(input: TokenStream) -> TokenStream {
         }
     };
 
-    TokenStream::from(expanded)
+    Ok(expanded)
+}
+
+// ----------------------------------------------------------------
+
+/// Try to derive the target of the deref-aware return type of a `#[getter(deref)]` field.
+///
+/// - `String` -> `str`
+/// - `Vec<T>` -> `[T]`
+/// - `Box<T>`/`Rc<T>`/`Arc<T>` -> `T`
+/// - `PathBuf` -> `Path`
+/// - `OsString` -> `OsStr`
+/// - `CString` -> `CStr`
+fn try_derive_deref_target(ty: &Type) -> Option<proc_macro2::TokenStream> {
+    if try_predicate_is_any_type(&["String"], 0, ty) {
+        return Some(quote! { str });
+    }
+    if try_predicate_is_any_type(&["PathBuf"], 0, ty) {
+        return Some(quote! { ::std::path::Path });
+    }
+    if try_predicate_is_any_type(&["OsString"], 0, ty) {
+        return Some(quote! { ::std::ffi::OsStr });
+    }
+    if try_predicate_is_any_type(&["CString"], 0, ty) {
+        return Some(quote! { ::std::ffi::CStr });
+    }
+    if try_predicate_is_any_type(&["Vec"], 1, ty) {
+        let inner_type = try_extract_type_arguments(ty)[0];
+        return Some(quote! { [#inner_type] });
+    }
+    if try_predicate_is_any_type(&["Box", "Rc", "Arc"], 1, ty) {
+        let inner_type = try_extract_type_arguments(ty)[0];
+        return Some(inner_type.to_token_stream());
+    }
+
+    None
+}
+
+/// `#[getter(deref)]`
+///
+/// - `String` -> `&str`
+/// - `Vec<T>` -> `&[T]`
+/// - `Box<T>`/`Rc<T>`/`Arc<T>` -> `&T`
+/// - `PathBuf` -> `&Path`
+fn derive_deref_getter(field_name: &syn::Ident, field_type: &Type) -> GetterBody {
+    match try_derive_deref_target(field_type) {
+        // Deref coercion isn't available in `const fn`.
+        Some(target) => GetterBody {
+            return_type: quote! { &#target },
+            body: quote! { &self.#field_name },
+            constant: false,
+        },
+        None => GetterBody {
+            return_type: quote! { &#field_type },
+            body: quote! { &self.#field_name },
+            constant: true,
+        },
+    }
+}
+
+/// `Option<T>`
+///
+/// - `Option<T>` -> `Option<&T>`
+/// - `#[getter(deref)]`
+///   - `Option<String>` -> `Option<&str>`
+///   - `Option<Vec<T>>` -> `Option<&[T]>`
+///   - ...
+fn derive_option_getter(field_name: &syn::Ident, field_type: &Type, deref: bool) -> GetterBody {
+    let inner_type = try_unwrap_option(field_type);
+
+    match try_derive_deref_target(inner_type) {
+        Some(target) if deref => GetterBody {
+            return_type: quote! { ::std::option::Option<&#target> },
+            body: quote! { self.#field_name.as_deref() },
+            constant: false,
+        },
+        _ => GetterBody {
+            return_type: quote! { ::std::option::Option<&#inner_type> },
+            body: quote! { self.#field_name.as_ref() },
+            constant: true,
+        },
+    }
+}
+
+/// `Option<T>`
+///
+/// - has_x() -> bool
+/// - x_or(&T) -> &T
+///   - `#[getter(copy)]`: x_or(T) -> T
+///   - `#[getter(deref)]`: `Option<String>` -> x_or(&str) -> &str, ...
+fn derive_option_getters(
+    vis: &syn::Visibility,
+    constant: bool,
+    field_name: &syn::Ident,
+    field_type: &Type,
+    copy: bool,
+    deref: bool,
+) -> Vec<proc_macro2::TokenStream> {
+    let inner_type = try_unwrap_option(field_type);
+    let constness = if constant {
+        quote! { const }
+    } else {
+        quote! {}
+    };
+
+    // has_x()
+    // |- has_email()
+    // |- ...
+    let has_getter_name = format_ident!("{}{}", OPTION_GETTER_PREFIX, field_name);
+    let has_getter = quote! {
+        #vis #constness fn #has_getter_name(&self) -> bool {
+            self.#field_name.is_some()
+        }
+    };
+
+    // x_or(..)
+    // |- email_or(..)
+    // |- ...
+    let or_getter_name = format_ident!("{}{}", field_name, OPTION_GETTER_OR_SUFFIX);
+    let or_getter = match try_derive_deref_target(inner_type) {
+        _ if copy => quote! {
+            #vis fn #or_getter_name(&self, default: #inner_type) -> #inner_type {
+                self.#field_name.unwrap_or(default)
+            }
+        },
+        Some(target) if deref => quote! {
+            #vis fn #or_getter_name<'__lombok>(&'__lombok self, default: &'__lombok #target) -> &'__lombok #target {
+                self.#field_name.as_deref().unwrap_or(default)
+            }
+        },
+        _ => quote! {
+            #vis fn #or_getter_name<'__lombok>(&'__lombok self, default: &'__lombok #inner_type) -> &'__lombok #inner_type {
+                self.#field_name.as_ref().unwrap_or(default)
+            }
+        },
+    };
+
+    vec![has_getter, or_getter]
+}
+
+/// `#[getter(lazy = path::to::init_fn)]`
+///
+/// - `OnceCell<T>`/`OnceLock<T>` -> `&T`, computed by `init_fn(&self)` on first access.
+fn try_derive_lazy_getter(
+    field_name: &syn::Ident,
+    field_type: &Type,
+    init_fn: &syn::Expr,
+) -> syn::Result<GetterBody> {
+    if !try_predicate_is_any_type(&["OnceCell", "OnceLock"], 1, field_type) {
+        return Err(syn::Error::new_spanned(
+            field_type,
+            "`#[getter(lazy = ...)]` expects a `OnceCell<T>` or `OnceLock<T>` field",
+        ));
+    }
+
+    let inner_type = try_extract_type_arguments(field_type)[0];
+
+    Ok(GetterBody {
+        return_type: quote! { &#inner_type },
+        body: quote! { self.#field_name.get_or_init(|| #init_fn(self)) },
+        constant: false,
+    })
+}
+
+/// `Cell<T>`, `RefCell<T>`, `Mutex<T>`, `RwLock<T>`, `Atomic*`
+///
+/// - `AtomicU32` -> `u32`, loaded with `SeqCst`
+/// - `#[getter(copy)]`, implied by scalar `T`: `Cell<T>`/`RefCell<T>`/`Mutex<T>`/`RwLock<T>` -> `T`
+/// - `RefCell<T>` -> `Ref<'_, T>`
+/// - `Mutex<T>` -> `MutexGuard<'_, T>`
+/// - `RwLock<T>` -> `RwLockReadGuard<'_, T>`
+///
+/// A non-`copy` `Cell<T>` can't be read through `&self`, it keeps the plain `&Cell<T>` getter.
+fn derive_interior_getter(
+    field_name: &syn::Ident,
+    interior: &Interior,
+    copy: bool,
+) -> Option<GetterBody> {
+    let value_type = &interior.value_type;
+    let read = interior.derive_read(field_name);
+
+    let (return_type, body) = match interior.kind {
+        InteriorKind::Atomic => (
+            quote! { #value_type },
+            quote! { self.#field_name.load(::std::sync::atomic::Ordering::SeqCst) },
+        ),
+        InteriorKind::Cell if copy => (quote! { #value_type }, quote! { self.#field_name.get() }),
+        InteriorKind::Cell => return None,
+        _ if copy => (quote! { #value_type }, quote! { *#read }),
+        InteriorKind::RefCell => (quote! { ::std::cell::Ref<'_, #value_type> }, read),
+        InteriorKind::Mutex => (quote! { ::std::sync::MutexGuard<'_, #value_type> }, read),
+        InteriorKind::RwLock => (
+            quote! { ::std::sync::RwLockReadGuard<'_, #value_type> },
+            read,
+        ),
+    };
+
+    Some(GetterBody {
+        return_type,
+        body,
+        constant: false,
+    })
+}
+
+/// Try to derive the target of the deref-aware return type of a `#[getter(mut, deref)]` field.
+///
+/// - `Vec<T>` -> `[T]`
+/// - `Box<T>` -> `T`
+///
+/// `&mut str` isn't useful and `Rc<T>`/`Arc<T>` aren't `DerefMut`, the others keep their type.
+fn try_derive_deref_mut_target(ty: &Type) -> Option<proc_macro2::TokenStream> {
+    if try_predicate_is_any_type(&["Vec"], 1, ty) {
+        let inner_type = try_extract_type_arguments(ty)[0];
+        return Some(quote! { [#inner_type] });
+    }
+    if try_predicate_is_any_type(&["Box"], 1, ty) {
+        let inner_type = try_extract_type_arguments(ty)[0];
+        return Some(inner_type.to_token_stream());
+    }
+
+    None
+}
+
+/// `#[getter(mut)]`
+///
+/// - `T` -> `&mut T`
+/// - `#[getter(mut, deref)]`
+///   - `Vec<T>` -> `&mut [T]`
+///   - `Box<T>` -> `&mut T`
+///   - `Option<T>` -> `Option<&mut T>`, `Option<Vec<T>>` -> `Option<&mut [T]>`, ...
+fn derive_mut_getter(
+    field_name: &syn::Ident,
+    field_type: &Type,
+    deref: bool,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    if deref && try_predicate_is_option(field_type) {
+        let inner_type = try_unwrap_option(field_type);
+        return match try_derive_deref_mut_target(inner_type) {
+            Some(target) => (
+                quote! { ::std::option::Option<&mut #target> },
+                quote! { self.#field_name.as_deref_mut() },
+            ),
+            None => (
+                quote! { ::std::option::Option<&mut #inner_type> },
+                quote! { self.#field_name.as_mut() },
+            ),
+        };
+    }
+
+    match try_derive_deref_mut_target(field_type) {
+        Some(target) if deref => (quote! { &mut #target }, quote! { &mut self.#field_name }),
+        _ => (
+            quote! { &mut #field_type },
+            quote! { &mut self.#field_name },
+        ),
+    }
 }
@@ -0,0 +1,331 @@
+/*
+ * Copyright © 2024 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// history
+
+// ----------------------------------------------------------------
+
+use proc_macro::TokenStream;
+
+use quote::{format_ident, quote};
+use syn::parse::Parser;
+use syn::{
+    parse_quote, Data, DataStruct, DeriveInput, Expr, ExprLit, Field, Fields, Generics, Ident, Lit,
+    Type,
+};
+
+use crate::attribute::{
+    derive_hidden_field_impls, try_select_fields, ArgShape, AttributeArgs, AttributeKey,
+};
+use crate::setter::{SETTER_ATTR_NAME, SETTER_KEYS};
+
+// ----------------------------------------------------------------
+
+/// The hidden undo/redo history field.
+pub(crate) const HISTORY_FIELD: &str = "__lombok_history";
+/// `#[setter(history)]`, added by `#[history]` for `Setter`.
+pub(crate) const ATTR_HISTORY: &str = "history";
+
+const HISTORY_ATTR_NAME: &str = "history";
+const HISTORY_ATTR_CAPACITY: &str = "capacity";
+const HISTORY_DEFAULT_CAPACITY: usize = 64;
+
+const HISTORY_KEYS: &[&[AttributeKey]] = &[&[(HISTORY_ATTR_CAPACITY, ArgShape::Expr)]];
+
+// ----------------------------------------------------------------
+
+pub(crate) fn derive_history(args: TokenStream, input: TokenStream) -> TokenStream {
+    let derive_input = syn::parse_macro_input!(input as DeriveInput);
+
+    match try_derive_history(args.into(), derive_input) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+fn try_derive_history(
+    args: proc_macro2::TokenStream,
+    mut derive_input: DeriveInput,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let args = AttributeArgs::try_parse_args(HISTORY_ATTR_NAME, HISTORY_KEYS, args)?;
+    let capacity = match args.try_expr(HISTORY_ATTR_CAPACITY)? {
+        None => HISTORY_DEFAULT_CAPACITY,
+        Some(Expr::Lit(ExprLit {
+            lit: Lit::Int(capacity),
+            ..
+        })) if capacity.base10_parse::<usize>()? > 0 => capacity.base10_parse()?,
+        Some(capacity) => {
+            return Err(syn::Error::new_spanned(
+                capacity,
+                "expected a positive `capacity = N`",
+            ))
+        }
+    };
+
+    let struct_name = derive_input.ident.clone();
+    let record_name = derive_record_name(&struct_name);
+    let history_name = format_ident!("__Lombok{}History", struct_name);
+    let history = format_ident!("{}", HISTORY_FIELD);
+
+    let Data::Struct(DataStruct {
+        fields: Fields::Named(named_fields),
+        ..
+    }) = &mut derive_input.data
+    else {
+        return Err(syn::Error::new_spanned(
+            &struct_name,
+            "`#[history]` expects a struct with named fields",
+        ));
+    };
+
+    // The fields with a setter, `Setter` rejects the interior-mutable ones.
+    let recorded_fields: Vec<(Ident, Type)> =
+        try_select_fields(SETTER_ATTR_NAME, SETTER_KEYS, &named_fields.named)?
+            .into_iter()
+            .map(|(field, _)| (field.ident.clone().unwrap(), field.ty.clone()))
+            .collect();
+    let field_names: Vec<&Ident> = recorded_fields
+        .iter()
+        .map(|(field_name, _)| field_name)
+        .collect();
+    let field_types: Vec<&Type> = recorded_fields
+        .iter()
+        .map(|(_, field_type)| field_type)
+        .collect();
+
+    let generics = derive_input.generics.clone();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let predicates = where_clause
+        .map(|where_clause| where_clause.predicates.iter().collect())
+        .unwrap_or_else(Vec::new);
+
+    // The uninhabited variant keeps every generic parameter in use.
+    let phantom_type = derive_phantom_type(&generics);
+    let phantom_variant = phantom_type.as_ref().map(|phantom_type| {
+        quote! { __LombokPhantom(::std::convert::Infallible, #phantom_type), }
+    });
+    let phantom_clone_arm = phantom_type.as_ref().map(|_| {
+        quote! { #record_name::__LombokPhantom(never, _) => match *never {}, }
+    });
+
+    let history_impls = derive_hidden_field_impls(&history_name, &generics);
+
+    // __lombok_history: __LombokXHistory<..>
+    let history_field = Field::parse_named.parse2(quote! {
+        #[doc(hidden)]
+        #history: #history_name #ty_generics
+    })?;
+    named_fields.named.push(history_field);
+
+    let setter_attr = format_ident!("{}", ATTR_HISTORY);
+    derive_input
+        .attrs
+        .push(parse_quote!(#[setter(#setter_attr)]));
+
+    let vis = &derive_input.vis;
+
+    let expanded = quote! {
+        #derive_input
+
+        /// The previous value of a field, see `#[history]`.
+        #[doc(hidden)]
+        #[allow(non_camel_case_types, dead_code)]
+        enum #record_name #generics #where_clause {
+            #(
+                #field_names(#field_types),
+            )*
+            #phantom_variant
+        }
+
+        impl #impl_generics ::std::clone::Clone for #record_name #ty_generics
+        where
+            #(#predicates,)*
+            #(for<'__lombok> #field_types: ::std::clone::Clone,)*
+        {
+            fn clone(&self) -> Self {
+                match self {
+                    #(
+                        #record_name::#field_names(value) => {
+                            #record_name::#field_names(::std::clone::Clone::clone(value))
+                        }
+                    )*
+                    #phantom_clone_arm
+                }
+            }
+        }
+
+        /// The bounded undo and the redo stacks, see `#[history]`.
+        ///
+        /// Every entry is `(id, record, state)`, the `state` of the field kept by `#[track_changes]`.
+        #[doc(hidden)]
+        struct #history_name #generics #where_clause {
+            undo: ::std::collections::VecDeque<(u64, #record_name #ty_generics, u64)>,
+            redo: ::std::vec::Vec<(u64, #record_name #ty_generics, u64)>,
+            /// The id of the latest record.
+            last: u64,
+            /// The id of the latest record dropped by the capacity.
+            base: u64,
+        }
+
+        impl #impl_generics #history_name #ty_generics #where_clause {
+            fn record(&mut self, record: #record_name #ty_generics, state: u64) {
+                self.redo.clear();
+                if self.undo.len() == #capacity {
+                    if let ::std::option::Option::Some((id, _, _)) = self.undo.pop_front() {
+                        self.base = id;
+                    }
+                }
+                self.last += 1;
+                self.undo.push_back((self.last, record, state));
+            }
+        }
+
+        impl #impl_generics ::std::default::Default for #history_name #ty_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    undo: ::std::collections::VecDeque::new(),
+                    redo: ::std::vec::Vec::new(),
+                    last: 0,
+                    base: 0,
+                }
+            }
+        }
+
+        impl #impl_generics ::std::clone::Clone for #history_name #ty_generics
+        where
+            #(#predicates,)*
+            for<'__lombok> #record_name #ty_generics: ::std::clone::Clone,
+        {
+            fn clone(&self) -> Self {
+                Self {
+                    undo: ::std::clone::Clone::clone(&self.undo),
+                    redo: ::std::clone::Clone::clone(&self.redo),
+                    last: self.last,
+                    base: self.base,
+                }
+            }
+        }
+
+        #history_impls
+
+        impl #impl_generics ::std::fmt::Debug for #history_name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.debug_struct(stringify!(#history_name))
+                    .field("undo", &self.undo.len())
+                    .field("redo", &self.redo.len())
+                    .finish()
+            }
+        }
+
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            /// Restore the value before the latest recorded change, `false` if there is none.
+            #vis fn undo(&mut self) -> bool {
+                match self.#history.undo.pop_back() {
+                    ::std::option::Option::Some((id, record, state)) => {
+                        let (record, state) = self.__lombok_swap_record(record, state);
+                        self.#history.redo.push((id, record, state));
+                        true
+                    }
+                    ::std::option::Option::None => false,
+                }
+            }
+
+            /// Reapply the latest undone change, `false` if there is none.
+            #vis fn redo(&mut self) -> bool {
+                match self.#history.redo.pop() {
+                    ::std::option::Option::Some((id, record, state)) => {
+                        let (record, state) = self.__lombok_swap_record(record, state);
+                        self.#history.undo.push_back((id, record, state));
+                        true
+                    }
+                    ::std::option::Option::None => false,
+                }
+            }
+
+            /// The number of changes `undo()` can restore.
+            #vis fn history_len(&self) -> usize {
+                self.#history.undo.len()
+            }
+
+            /// The current position in the history, for `rollback_to(..)`.
+            #vis fn checkpoint(&self) -> u64 {
+                self.#history
+                    .undo
+                    .back()
+                    .map_or(self.#history.base, |(id, _, _)| *id)
+            }
+
+            /// Undo the changes after `checkpoint`, `false` if it can't be restored exactly.
+            #vis fn rollback_to(&mut self, checkpoint: u64) -> bool {
+                while self.checkpoint() > checkpoint {
+                    if !self.undo() {
+                        break;
+                    }
+                }
+
+                self.checkpoint() == checkpoint
+            }
+        }
+    };
+
+    Ok(expanded)
+}
+
+/// `Row` -> `__LombokRowRecord`
+pub(crate) fn derive_record_name(struct_name: &Ident) -> Ident {
+    format_ident!("__Lombok{}Record", struct_name)
+}
+
+/// Record the previous value `old` of a field and its `#[track_changes]` `state`, see [`derive_history`].
+pub(crate) fn derive_record(
+    struct_name: &Ident,
+    field_name: &Ident,
+    old: proc_macro2::TokenStream,
+    state: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let history = format_ident!("{}", HISTORY_FIELD);
+    let record_name = derive_record_name(struct_name);
+
+    quote! {
+        self.#history.record(#record_name::#field_name(#old), #state);
+    }
+}
+
+/// The match arm of the uninhabited variant of the records, if `generics` needs one.
+pub(crate) fn derive_phantom_arm(
+    struct_name: &Ident,
+    generics: &Generics,
+) -> Option<proc_macro2::TokenStream> {
+    let record_name = derive_record_name(struct_name);
+
+    derive_phantom_type(generics)
+        .map(|_| quote! { #record_name::__LombokPhantom(never, _) => match never {}, })
+}
+
+/// `PhantomData<(&'a (), fn() -> T)>` over the lifetimes and type parameters of `generics`.
+///
+/// The records may not mention every parameter, e.g. one used by a `#[setter(skip)]` field only.
+fn derive_phantom_type(generics: &Generics) -> Option<proc_macro2::TokenStream> {
+    let lifetimes = generics.lifetimes().map(|param| &param.lifetime);
+    let types = generics.type_params().map(|param| &param.ident);
+    if generics.lifetimes().next().is_none() && generics.type_params().next().is_none() {
+        return None;
+    }
+
+    Some(quote! {
+        ::std::marker::PhantomData<(#(&#lifetimes (),)* #(fn() -> #types,)*)>
+    })
+}
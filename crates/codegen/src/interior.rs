@@ -0,0 +1,200 @@
+/*
+ * Copyright © 2024 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// interior
+
+// ----------------------------------------------------------------
+
+use quote::quote;
+use syn::{Ident, Type};
+
+use crate::attribute::{ArgShape, AttributeArgs, AttributeKey};
+use crate::types::{try_extract_type_arguments, try_extract_type_ident, try_predicate_is_any_type};
+
+// ----------------------------------------------------------------
+
+pub(crate) const ATTR_INTERIOR: &str = "interior";
+pub(crate) const ATTR_POISON: &str = "poison";
+
+/// `#[getter(..)]`/`#[setter(..)]`: `interior` and `poison = "..."`.
+pub(crate) const INTERIOR_KEYS: &[AttributeKey] = &[
+    (ATTR_INTERIOR, ArgShape::Flag),
+    (ATTR_POISON, ArgShape::Str),
+];
+
+/// `AtomicX` -> the primitive type it loads and stores.
+const ATOMIC_TYPES: &[(&str, &str)] = &[
+    ("AtomicBool", "bool"),
+    ("AtomicU8", "u8"),
+    ("AtomicU16", "u16"),
+    ("AtomicU32", "u32"),
+    ("AtomicU64", "u64"),
+    ("AtomicUsize", "usize"),
+    ("AtomicI8", "i8"),
+    ("AtomicI16", "i16"),
+    ("AtomicI32", "i32"),
+    ("AtomicI64", "i64"),
+    ("AtomicIsize", "isize"),
+];
+
+// ----------------------------------------------------------------
+
+/// The interior-mutable wrappers accessed through `&self`.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum InteriorKind {
+    /// `Cell<T>`
+    Cell,
+    /// `RefCell<T>`
+    RefCell,
+    /// `Mutex<T>`
+    Mutex,
+    /// `RwLock<T>`
+    RwLock,
+    /// `AtomicBool`, `AtomicU32`, ...
+    Atomic,
+}
+
+/// `#[getter(poison = "panic" | "recover")]`, `#[setter(poison = "panic" | "recover")]`
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum PoisonPolicy {
+    /// Panic on a poisoned `Mutex`/`RwLock`.
+    Panic,
+    /// Take the value guarded by a poisoned `Mutex`/`RwLock` anyway.
+    Recover,
+}
+
+/// An interior-mutable field, e.g. `Mutex<T>` or `Arc<RwLock<T>>`.
+pub(crate) struct Interior {
+    pub(crate) kind: InteriorKind,
+    /// The value read and written through the wrapper: `T` of `Mutex<T>`, `u32` of `AtomicU32`, ...
+    pub(crate) value_type: Type,
+    pub(crate) poison: PoisonPolicy,
+}
+
+impl Interior {
+    /// Try to parse the interior-mutable wrapper of a field, opted in by `#[x(interior)]`.
+    ///
+    /// On the field, `#[x(interior)]` expects a wrapper and `#[x(interior = false)]` opts out.
+    /// On the struct, it switches every wrapper field. One `Rc`/`Arc` layer is looked through.
+    pub(crate) fn try_parse(
+        field_args: &AttributeArgs,
+        struct_args: &AttributeArgs,
+        field_type: &Type,
+    ) -> syn::Result<Option<Self>> {
+        let requested = field_args.try_flag(ATTR_INTERIOR)?;
+        if !requested.map_or(
+            struct_args.try_flag(ATTR_INTERIOR)? == Some(true),
+            |requested| requested,
+        ) {
+            return Ok(None);
+        }
+
+        let Some((kind, value_type)) = try_predicate_interior_kind(field_type) else {
+            if requested == Some(true) {
+                return Err(syn::Error::new_spanned(
+                    field_type,
+                    "`interior` expects a `Cell`, `RefCell`, `Mutex`, `RwLock` or `Atomic*` field",
+                ));
+            }
+            return Ok(None);
+        };
+
+        let poison = match field_args.try_str_or(struct_args, ATTR_POISON)? {
+            None => PoisonPolicy::Panic,
+            Some(poison) => match poison.value().as_str() {
+                "panic" => PoisonPolicy::Panic,
+                "recover" => PoisonPolicy::Recover,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        poison,
+                        r#"expected `poison = "panic" | "recover"`"#,
+                    ))
+                }
+            },
+        };
+
+        Ok(Some(Self {
+            kind,
+            value_type,
+            poison,
+        }))
+    }
+
+    /// The `Ref`/`MutexGuard`/`RwLockReadGuard` of a `RefCell`/`Mutex`/`RwLock` field.
+    pub(crate) fn derive_read(&self, field_name: &Ident) -> proc_macro2::TokenStream {
+        match self.kind {
+            InteriorKind::RefCell => quote! { self.#field_name.borrow() },
+            InteriorKind::RwLock => self.derive_lock(quote! { self.#field_name.read() }),
+            _ => self.derive_lock(quote! { self.#field_name.lock() }),
+        }
+    }
+
+    /// The `RefMut`/`MutexGuard`/`RwLockWriteGuard` of a `RefCell`/`Mutex`/`RwLock` field.
+    pub(crate) fn derive_write(&self, field_name: &Ident) -> proc_macro2::TokenStream {
+        match self.kind {
+            InteriorKind::RefCell => quote! { self.#field_name.borrow_mut() },
+            InteriorKind::RwLock => self.derive_lock(quote! { self.#field_name.write() }),
+            _ => self.derive_lock(quote! { self.#field_name.lock() }),
+        }
+    }
+
+    fn derive_lock(&self, lock: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match self.poison {
+            PoisonPolicy::Panic => quote! { #lock.unwrap() },
+            PoisonPolicy::Recover => {
+                quote! { #lock.unwrap_or_else(::std::sync::PoisonError::into_inner) }
+            }
+        }
+    }
+}
+
+// ----------------------------------------------------------------
+
+/// Try to predicate the [`InteriorKind`] and the value type of [`syn::Type`].
+///
+/// - `Cell<u32>` -> `(Cell, u32)`
+/// - `Arc<Mutex<String>>` -> `(Mutex, String)`
+/// - `AtomicBool` -> `(Atomic, bool)`
+fn try_predicate_interior_kind(ty: &Type) -> Option<(InteriorKind, Type)> {
+    let ty = if try_predicate_is_any_type(&["Rc", "Arc"], 1, ty) {
+        try_extract_type_arguments(ty)[0]
+    } else {
+        ty
+    };
+
+    let wrappers = [
+        ("Cell", InteriorKind::Cell),
+        ("RefCell", InteriorKind::RefCell),
+        ("Mutex", InteriorKind::Mutex),
+        ("RwLock", InteriorKind::RwLock),
+    ];
+    for (wrapper, kind) in wrappers {
+        if try_predicate_is_any_type(&[wrapper], 1, ty) {
+            return Some((kind, try_extract_type_arguments(ty)[0].clone()));
+        }
+    }
+
+    let ident = try_extract_type_ident(ty)?;
+    ATOMIC_TYPES
+        .iter()
+        .find(|(atomic, _)| ident == atomic && try_extract_type_arguments(ty).is_empty())
+        .map(|(_, primitive)| {
+            (
+                InteriorKind::Atomic,
+                syn::parse_str(primitive).expect("primitive type"),
+            )
+        })
+}
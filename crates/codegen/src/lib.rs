@@ -23,14 +23,39 @@ extern crate proc_macro;
 use proc_macro::TokenStream;
 
 use crate::builder::derive_builder;
+use crate::constructor::{
+    derive_all_args_constructor, derive_no_args_constructor, derive_required_args_constructor,
+};
+use crate::data::derive_data;
+use crate::eq::derive_equals_and_hash_code;
 use crate::getter::derive_getter;
+use crate::history::derive_history;
+use crate::listeners::derive_change_listeners;
+use crate::sensitive::derive_redacted_debug;
 use crate::setter::derive_setter;
+use crate::to_string::derive_to_string;
+use crate::track::derive_track_changes;
+use crate::value::derive_value;
+use crate::with::derive_with;
 
 // ----------------------------------------------------------------
 
+mod attribute;
 mod builder;
+mod constructor;
+mod data;
+mod eq;
 mod getter;
+mod history;
+mod interior;
+mod listeners;
+mod sensitive;
 mod setter;
+mod to_string;
+mod track;
+mod types;
+mod value;
+mod with;
 
 // ----------------------------------------------------------------
 
@@ -97,15 +122,225 @@ mod setter;
 ///     user.set_email("lombokrs@gmail.com".to_string());
 ///     user.set_hobby(vec!["football".to_string()]);
 ///
-///     assert_eq!(&9527u32, user.get_id());
-///     assert_eq!(&25u8, user.get_age());
+///     assert_eq!(9527u32, user.get_id());
+///     assert_eq!(25u8, user.get_age());
 ///
 ///     assert_eq!("lombokrs", user.get_name());
 ///     assert_eq!("lombokrs@gmail.com", user.get_email());
 ///     assert_eq!(&vec!["football".to_string()], user.get_hobby());
 ///
 /// ```
-#[proc_macro_derive(Setter)]
+///
+/// # Option
+///
+/// `Option<T>` fields get `set_x(T)`, `clear_x()` and `take_x() -> Option<T>`.
+///
+/// ```rust
+/// use lombokrs_codegen::Setter;
+///
+/// #[derive(Setter, Debug)]
+/// pub struct Contact {
+///     email: Option<String>,
+/// }
+///
+/// let mut contact = Contact { email: None };
+///
+/// contact.set_email("photowey@gmail.com".to_string());
+/// assert_eq!(Some("photowey@gmail.com".to_string()), contact.take_email());
+///
+/// contact.clear_email();
+/// assert_eq!(None, contact.email);
+/// ```
+///
+/// # Chain and fluent
+///
+/// `#[setter(chain)]`, on the struct or on a field, makes setters return `&mut Self`.
+/// `#[setter(fluent)]` names them `x(..)` instead of `set_x(..)`, which clashes with the fluent getters
+/// unless those use `#[getter(style = "prefixed")]`.
+///
+/// ```rust
+/// use lombokrs_codegen::{Getter, Setter};
+///
+/// #[derive(Setter, Getter, Debug)]
+/// #[getter(style = "prefixed")]
+/// #[setter(chain)]
+/// pub struct Settings {
+///     theme: String,
+///     font_size: u8,
+///     #[setter(fluent)]
+///     locale: String,
+/// }
+///
+/// let mut settings = Settings { theme: "light".to_string(), font_size: 12, locale: "en".to_string() };
+///
+/// settings
+///     .set_theme("dark".to_string())
+///     .set_font_size(14)
+///     .locale("zh-CN".to_string());
+///
+/// assert_eq!("zh-CN", settings.get_locale());
+/// ```
+///
+/// # Collection
+///
+/// `#[setter(collection = "activity")]`, or `#[builder(method = "activity")]`, adds element methods
+/// to `Vec`, `VecDeque`, `HashSet`, `BTreeSet`, `HashMap` and `BTreeMap` fields:
+///
+/// - add_activity(..), or insert_activity(key, value) for maps
+/// - extend_activities(..)
+/// - remove_activity_at(index) for `Vec`/`VecDeque`, remove_activity(&..) for sets and maps
+/// - clear_activities()
+///
+/// A bare `#[setter(collection)]` names them after the builder method, or the field itself.
+/// `#[setter(collection = false)]` turns them off.
+///
+/// ```rust
+/// use std::collections::HashMap;
+///
+/// use lombokrs_codegen::{Getter, Setter};
+///
+/// #[derive(Setter, Getter, Debug)]
+/// #[setter(chain)]
+/// pub struct Team {
+///     #[setter(collection = "member")]
+///     members: Vec<String>,
+///     #[setter(collection = "role")]
+///     roles: HashMap<String, u8>,
+/// }
+///
+/// let mut team = Team { members: Vec::new(), roles: HashMap::new() };
+///
+/// team.add_member("photowey".to_string())
+///     .extend_members(vec!["rust".to_string()])
+///     .insert_role("photowey".to_string(), 1);
+///
+/// assert_eq!("rust", team.remove_member_at(1));
+/// assert_eq!(Some(1), team.remove_role(&"photowey".to_string()));
+/// assert_eq!(&vec!["photowey".to_string()], team.members());
+/// ```
+///
+/// # Replace and update
+///
+/// `#[setter(replace)]` adds `replace_x(..)`, which returns the previous value,
+/// and `#[setter(update)]` adds `update_x(|x| ..)`, which mutates the value in place.
+///
+/// ```rust
+/// use lombokrs_codegen::{Getter, Setter};
+///
+/// #[derive(Setter, Getter, Debug)]
+/// #[setter(replace, update)]
+/// pub struct Connection {
+///     state: String,
+///     retries: u32,
+/// }
+///
+/// let mut connection = Connection { state: "idle".to_string(), retries: 0 };
+///
+/// assert_eq!("idle", connection.replace_state("connecting".to_string()));
+/// connection.update_retries(|retries| *retries += 1);
+///
+/// assert_eq!("connecting", connection.state());
+/// assert_eq!(1u32, connection.retries());
+/// ```
+///
+/// # Notify
+///
+/// `#[setter(notify)]`, on the struct or on a field, makes every setter call `self.on_change(field, &old, &new)`
+/// after the assignment. `#[setter(notify = on_title_change)]` calls another method, `notify = false` opts a field out.
+/// The hook is written by hand and takes `&self`, e.g. `fn on_change<T: Debug + ?Sized>(&self, ..)` or
+/// `fn on_change(&self, field: &str, old: &dyn Any, new: &dyn Any)`. [`change_listeners`] registers closures instead.
+///
+/// The collection methods and `update_x(..)` keep a clone of the previous value, the `&self` setters of
+/// interior-mutable fields a clone of the new one: they need `T: Clone`.
+///
+/// ```rust
+/// use std::cell::RefCell;
+/// use std::fmt::Debug;
+///
+/// use lombokrs_codegen::Setter;
+///
+/// #[derive(Setter, Debug, Default)]
+/// #[setter(notify)]
+/// pub struct ViewModel {
+///     title: String,
+///     #[setter(skip)]
+///     renders: RefCell<Vec<String>>,
+/// }
+///
+/// impl ViewModel {
+///     fn on_change<T: Debug + ?Sized>(&self, field: &'static str, old: &T, new: &T) {
+///         self.renders.borrow_mut().push(format!("{}: {:?} -> {:?}", field, old, new));
+///     }
+/// }
+///
+/// let mut view_model = ViewModel::default();
+/// view_model.set_title("lombokrs".to_string());
+///
+/// assert_eq!(vec![r#"title: "" -> "lombokrs""#], *view_model.renders.borrow());
+/// ```
+///
+/// # Interior
+///
+/// `#[setter(interior)]` on a `Cell<T>`, `RefCell<T>`, `Mutex<T>`, `RwLock<T>` or `Atomic*` field,
+/// also behind an `Rc`/`Arc`, generates `&self` setters writing through the wrapper, and `&Self`
+/// is returned with `#[setter(chain)]`. On the struct, it switches every such field,
+/// `#[setter(interior = false)]` on a field keeps its plain `&mut self` setter.
+/// `replace_x(..)` and `update_x(|x| ..)` work the same way, atomics have no `update_x(..)`.
+///
+/// A poisoned lock panics, `#[setter(poison = "recover")]` writes it anyway.
+///
+/// ```rust
+/// use std::cell::Cell;
+/// use std::sync::atomic::AtomicBool;
+/// use std::sync::Mutex;
+///
+/// use lombokrs_codegen::{Getter, Setter};
+///
+/// #[derive(Setter, Getter, Debug)]
+/// #[getter(interior)]
+/// #[setter(chain, update, interior)]
+/// pub struct Service {
+///     hits: Cell<u32>,
+///     running: AtomicBool,
+///     peers: Mutex<Vec<String>>,
+/// }
+///
+/// let service = Service { hits: Cell::new(0), running: AtomicBool::new(false), peers: Mutex::new(Vec::new()) };
+///
+/// service
+///     .update_hits(|hits| *hits += 1)
+///     .set_running(true)
+///     .update_peers(|peers| peers.push("127.0.0.1".to_string()));
+///
+/// assert_eq!(1u32, service.hits());
+/// assert!(service.is_running());
+/// assert_eq!(vec!["127.0.0.1"], *service.peers());
+/// ```
+///
+/// # Selection
+///
+/// - `#[setter(skip)]` on a field generates no setter for it.
+/// - `#[setter(only)]` on some fields generates setters for those fields only.
+///
+/// # Visibility
+///
+/// Setters inherit the visibility of their field. `#[setter(vis = "...")]`, on the struct
+/// or on a field, overrides it: `#[setter(vis = "pub")]` makes every setter `pub`, `vis = ""` private.
+///
+/// # Options
+///
+/// An unknown option, or a value of the wrong shape, is a compile error in every helper attribute:
+///
+/// ```rust,compile_fail
+/// use lombokrs_codegen::Setter;
+///
+/// #[derive(Setter)]
+/// #[setter(chian)]
+/// pub struct User {
+///     name: String,
+/// }
+/// ```
+#[proc_macro_derive(Setter, attributes(setter))]
 pub fn setter_derive(input: TokenStream) -> TokenStream {
     derive_setter(input)
 }
@@ -170,14 +405,240 @@ pub fn setter_derive(input: TokenStream) -> TokenStream {
 ///         vec!["badminton".to_string()],
 ///     );
 ///
-///     assert_eq!(&10086u32, user.get_id());
-///     assert_eq!(&18u8, user.get_age());
+///     assert_eq!(10086u32, user.get_id());
+///     assert_eq!(18u8, user.get_age());
 ///
 ///     assert_eq!("photowey", user.get_name());
 ///     assert_eq!("photowey@gmail.com", user.get_email());
 ///     assert_eq!(&vec!["badminton".to_string()], user.get_hobby());
 /// ```
-#[proc_macro_derive(Getter)]
+///
+/// # Deref
+///
+/// `#[getter(deref)]`, on the struct or on a field, returns the deref target instead of the storage type:
+///
+/// - `String` -> `&str`
+/// - `Vec<T>` -> `&[T]`
+/// - `Option<T>` -> `Option<&T>`
+/// - `Box<T>`/`Rc<T>`/`Arc<T>` -> `&T`
+/// - `PathBuf` -> `&Path`
+///
+/// ```rust
+/// use std::path::{Path, PathBuf};
+///
+/// use lombokrs_codegen::Getter;
+///
+/// #[derive(Getter, Debug)]
+/// #[getter(deref)]
+/// pub struct Profile {
+///     nickname: String,
+///     tags: Vec<String>,
+///     bio: Option<String>,
+///     home: PathBuf,
+///     #[getter(deref = false)]
+///     email: String,
+/// }
+///
+/// // ----------------------------------------------------------------
+///
+/// // Usage:
+///
+///     let profile = Profile {
+///         nickname: "photowey".to_string(),
+///         tags: vec!["rust".to_string()],
+///         bio: None,
+///         home: PathBuf::from("/home/photowey"),
+///         email: "photowey@gmail.com".to_string(),
+///     };
+///
+///     let nickname: &str = profile.get_nickname();
+///     let tags: &[String] = profile.get_tags();
+///     let bio: Option<&str> = profile.get_bio();
+///     let home: &Path = profile.get_home();
+///     let email: &String = profile.get_email();
+/// ```
+///
+/// # Copy and const
+///
+/// Getters of primitive scalars (`u32`, `bool`, `char`, `f64`, ...) return by value.
+/// `#[getter(copy)]` does the same for any other `Copy` type, `#[getter(copy = false)]` returns a reference.
+///
+/// `#[getter(const)]` generates `const fn` getters wherever the body allows it,
+/// deref-aware getters such as `String` -> `&str` stay non-`const`.
+///
+/// ```rust
+/// use lombokrs_codegen::Getter;
+///
+/// #[derive(Getter, Debug)]
+/// #[getter(const)]
+/// pub struct Version {
+///     major: u32,
+///     #[getter(copy)]
+///     stage: (char, u8),
+/// }
+///
+/// const VERSION: Version = Version { major: 0, stage: ('b', 1) };
+/// const MAJOR: u32 = VERSION.get_major();
+///
+/// assert_eq!(('b', 1), VERSION.stage());
+/// ```
+///
+/// # Option
+///
+/// `Option<T>` fields get `has_x() -> bool`, `x() -> Option<&T>` and `x_or(&T) -> &T`.
+///
+/// ```rust
+/// use lombokrs_codegen::Getter;
+///
+/// #[derive(Getter, Debug)]
+/// pub struct Contact {
+///     email: Option<String>,
+/// }
+///
+/// let contact = Contact { email: None };
+///
+/// assert!(!contact.has_email());
+/// assert_eq!(None, contact.email());
+/// assert_eq!("-", contact.email_or(&"-".to_string()));
+/// ```
+///
+/// # Predicate
+///
+/// `bool` fields get `is_x()` instead of `get_x()`, returning by value; a field already named
+/// `is_x` keeps its name. `#[getter(predicate)]` does the same for other `Copy` types,
+/// `#[getter(predicate = false)]` restores `get_x()`.
+///
+/// ```rust
+/// use lombokrs_codegen::Getter;
+///
+/// #[derive(Getter, Debug)]
+/// pub struct Feature {
+///     enabled: bool,
+///     is_beta: bool,
+/// }
+///
+/// let feature = Feature { enabled: true, is_beta: false };
+///
+/// assert!(feature.is_enabled());
+/// assert!(feature.enabled());
+/// assert!(!feature.is_beta());
+/// ```
+///
+/// # Mut
+///
+/// `#[getter(mut)]` adds `get_x_mut()` and `x_mut()`, returning `&mut T`.
+/// Combined with `deref`, `Vec<T>` returns `&mut [T]`, `Box<T>` `&mut T` and `Option<T>` `Option<&mut T>`.
+///
+/// ```rust
+/// use lombokrs_codegen::Getter;
+///
+/// #[derive(Getter, Debug)]
+/// #[getter(mut)]
+/// pub struct Playlist {
+///     name: String,
+///     #[getter(deref)]
+///     tracks: Vec<String>,
+/// }
+///
+/// let mut playlist = Playlist { name: "lombokrs".to_string(), tracks: vec![] };
+///
+/// playlist.get_name_mut().push_str("-rs");
+/// let tracks: &mut [String] = playlist.tracks_mut();
+///
+/// assert_eq!("lombokrs-rs", playlist.name());
+/// ```
+///
+/// # Selection
+///
+/// - `#[getter(skip)]` on a field generates no getters for it.
+/// - `#[getter(only)]` on some fields generates getters for those fields only.
+/// - `#[getter(style = "prefixed" | "fluent" | "both")]`, on the struct or on a field,
+///   generates `get_x()`, `x()` or both (the default).
+///
+/// ```rust
+/// use lombokrs_codegen::Getter;
+///
+/// #[derive(Getter, Debug)]
+/// #[getter(style = "fluent")]
+/// pub struct Account {
+///     id: u32,
+///     #[getter(style = "prefixed")]
+///     name: String,
+///     #[getter(skip)]
+///     password: String,
+/// }
+///
+/// let account = Account { id: 10086, name: "photowey".to_string(), password: "***".to_string() };
+///
+/// assert_eq!(10086, account.id());
+/// assert_eq!("photowey", account.get_name());
+/// ```
+///
+/// # Lazy
+///
+/// `#[getter(lazy = path::to::init_fn)]` on a `OnceCell<T>` or `OnceLock<T>` field returns `&T`,
+/// computed by `init_fn(&self)` on first access and cached afterwards.
+/// `OnceLock<T>` makes the initialization thread-safe.
+///
+/// ```rust
+/// use std::sync::OnceLock;
+///
+/// use lombokrs_codegen::Getter;
+///
+/// #[derive(Getter, Debug)]
+/// pub struct Document {
+///     text: String,
+///     #[getter(lazy = Document::count_words)]
+///     words: OnceLock<usize>,
+/// }
+///
+/// impl Document {
+///     fn count_words(&self) -> usize {
+///         self.text.split_whitespace().count()
+///     }
+/// }
+///
+/// let document = Document { text: "hello lombokrs".to_string(), words: OnceLock::new() };
+///
+/// assert_eq!(&2, document.words());
+/// ```
+///
+/// # Interior
+///
+/// `#[getter(interior)]` on a `Cell<T>`, `RefCell<T>`, `Mutex<T>`, `RwLock<T>` or `Atomic*` field,
+/// also behind an `Rc`/`Arc`, reads it through the wrapper. On the struct, it switches every such field,
+/// `#[getter(interior = false)]` on a field keeps its plain `&Mutex<T>` getter.
+///
+/// - `AtomicU64` -> `u64`, `AtomicBool` -> `is_x() -> bool`
+/// - scalar or `#[getter(copy)]` `T` -> `T`
+/// - otherwise `Ref<'_, T>`, `MutexGuard<'_, T>` or `RwLockReadGuard<'_, T>`
+///
+/// A poisoned lock panics, `#[getter(poison = "recover")]` reads it anyway.
+///
+/// ```rust
+/// use std::sync::atomic::AtomicBool;
+/// use std::sync::{Arc, Mutex};
+///
+/// use lombokrs_codegen::Getter;
+///
+/// #[derive(Getter, Debug)]
+/// #[getter(interior)]
+/// pub struct Service {
+///     running: AtomicBool,
+///     peers: Arc<Mutex<Vec<String>>>,
+/// }
+///
+/// let service = Service { running: AtomicBool::new(true), peers: Arc::new(Mutex::new(Vec::new())) };
+///
+/// assert!(service.is_running());
+/// assert!(service.peers().is_empty());
+/// ```
+///
+/// # Visibility
+///
+/// Getters inherit the visibility of their field. `#[getter(vis = "...")]`, on the struct
+/// or on a field, overrides it: `#[getter(vis = "pub")]` makes every getter `pub`, `vis = ""` private.
+#[proc_macro_derive(Getter, attributes(getter))]
 pub fn getter_derive(input: TokenStream) -> TokenStream {
     derive_getter(input)
 }
@@ -229,8 +690,8 @@ pub fn getter_derive(input: TokenStream) -> TokenStream {
 ///         .build()
 ///         .unwrap();
 ///
-///     assert_eq!(&10086u32, user.get_id());
-///     assert_eq!(&18u8, user.get_age());
+///     assert_eq!(10086u32, user.get_id());
+///     assert_eq!(18u8, user.get_age());
 ///
 ///     assert_eq!("photowey", user.get_name());
 ///     assert_eq!("photowey@gmail.com", user.get_email());
@@ -253,20 +714,659 @@ pub fn getter_derive(input: TokenStream) -> TokenStream {
 ///     // Missing field: `id`!
 ///     assert!(rvt.is_err())
 /// ```
-#[proc_macro_derive(Builder, attributes(builder))]
+///
+/// `Option` and `Vec` fields may be left unset, they build as `None` and empty. Every other field,
+/// `VecDeque`, sets and maps included, is required by `build()` unless it has a `#[builder(default)]`.
+/// `#[builder(method = "...")]` adds an element method for `Vec` fields. A `Vec` field with a default
+/// builds as its default only when left unset, setting it starts from empty instead.
+///
+/// `XxxBuilder` implements `Debug` when the fields do, printing the `#[sensitive]` ones as `Some(***)` or `None`.
+///
+/// # Generics
+///
+/// Type, lifetime and const generics, bounds and default type parameters are carried over
+/// to `XxxBuilder` as declared, so generic fields don't have to be `Clone`.
+///
+/// With `#[builder(type_changing)]` on a field declared as a bare, unbounded type parameter, or
+/// `#[builder(type_changing = "B")]` on the struct, the setter of that field switches the parameter
+/// of the builder to the type of its argument. Such parameters start out as `()`. A bound on them,
+/// e.g. `where B: Clone`, is a compile error since `()` would have to satisfy it, put the bound
+/// on the `impl` blocks instead. The other type parameters and their bounds are left alone.
+///
+/// ```rust
+/// use lombokrs_codegen::Builder;
+///
+/// #[derive(Builder, Debug)]
+/// pub struct Buf<T, const N: usize> {
+///     data: [T; N],
+///     len: usize,
+/// }
+///
+/// #[derive(Builder, Debug)]
+/// pub struct Request<B = ()> {
+///     url: String,
+///     #[builder(type_changing)]
+///     body: B,
+/// }
+///
+/// #[derive(Builder, Debug)]
+/// #[builder(type_changing = "B")]
+/// pub struct Upload<'a, T: Clone, B> {
+///     url: &'a str,
+///     meta: T,
+///     body: B,
+/// }
+///
+/// // ----------------------------------------------------------------
+///
+/// // Usage:
+///
+///     let buf: Buf<u8, 4> = Buf::builder().data([1, 2, 3, 4]).len(4).build().unwrap();
+///
+///     let request: Request<&str> = Request::builder()
+///         .url("https://github.com/photowey/lombokrs".to_string())
+///         // RequestBuilder<()> -> RequestBuilder<&str>
+///         .body("{}")
+///         .build()
+///         .unwrap();
+///
+///     let upload: Upload<'_, u32, Vec<u8>> = Upload::builder()
+///         .url("https://github.com/photowey/lombokrs")
+///         .meta(1)
+///         // UploadBuilder<'_, u32, ()> -> UploadBuilder<'_, u32, Vec<u8>>
+///         .body(vec![0x2a])
+///         .build()
+///         .unwrap();
+/// ```
+///
+/// ```rust,compile_fail
+/// use lombokrs_codegen::Builder;
+///
+/// #[derive(Builder, Debug)]
+/// pub struct Request<B>
+/// where
+///     B: Clone,
+/// {
+///     #[builder(type_changing)]
+///     body: B,
+/// }
+/// ```
+#[proc_macro_derive(Builder, attributes(builder, default))]
 pub fn builder_derive(input: TokenStream) -> TokenStream {
     derive_builder(input)
 }
 
+/// `With` is a macro that helps you automatically generate wither methods for structs,
+/// copies with one field changed.
+///
+/// - with_x(self, ..) -> Self
+///   - with_id(..)
+///   - ...
+/// - with_x_cloned(&self, ..) -> Self, for `Clone` types
+///   - with_id_cloned(..)
+///   - ...
+///
+/// `#[with(skip)]`, `#[with(only)]` and `#[with(vis = "...")]` work like their `Getter` counterparts.
+///
+/// # Examples
+///
+/// ```rust
+/// use lombokrs_codegen::With;
+///
+/// #[derive(With, Clone, Debug, PartialEq)]
+/// pub struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// // ----------------------------------------------------------------
+///
+/// // Usage:
+///
+///     let origin = Point { x: 0, y: 0 };
+///
+///     let moved = origin.with_x_cloned(3);
+///     assert_eq!(Point { x: 0, y: 0 }, origin);
+///
+///     let moved = moved.with_y(4);
+///     assert_eq!(Point { x: 3, y: 4 }, moved);
+/// ```
+#[proc_macro_derive(With, attributes(with))]
+pub fn with_derive(input: TokenStream) -> TokenStream {
+    derive_with(input)
+}
+
+/// `NoArgsConstructor` is a macro that generates `Default` and a constructor taking no arguments.
+///
+/// - new() -> Self
+/// - impl Default
+///
+/// Every field starts out as its `#[default(expr)]`, or `Default::default()`. `#[default = literal]`
+/// is short for literals, string literals are converted with `Into` like `SmartDefault`. A bare
+/// `#[default]` spells out `Default::default()`, e.g. to make a [`Builder`] field optional.
+/// [`Builder`] and [`RequiredArgsConstructor`] fall back to the same default for the fields left unset.
+///
+/// `#[no_args(name = "...", vis = "...")]` works like `#[all_args(..)]` of [`AllArgsConstructor`].
+///
+/// ```rust
+/// use lombokrs_codegen::{Getter, NoArgsConstructor};
+///
+/// #[derive(NoArgsConstructor, Getter, Debug)]
+/// pub struct Config {
+///     #[default = "anonymous"]
+///     name: String,
+///     #[default = 8080]
+///     port: u16,
+///     verbose: bool,
+/// }
+///
+/// let config = Config::new();
+///
+/// assert_eq!("anonymous", config.get_name());
+/// assert_eq!(8080u16, config.get_port());
+/// assert!(!config.is_verbose());
+/// assert_eq!(8080u16, Config::default().get_port());
+/// ```
+#[proc_macro_derive(NoArgsConstructor, attributes(no_args, default))]
+pub fn no_args_constructor_derive(input: TokenStream) -> TokenStream {
+    derive_no_args_constructor(input)
+}
+
+/// `AllArgsConstructor` is a macro that generates a constructor taking every field in declaration order.
+///
+/// - new(id, name, ...) -> Self
+///
+/// `#[new(into)]`, on the struct or on a field, takes `impl Into<T>` instead of `T`.
+/// `#[all_args(name = "create")]` renames the constructor, and `#[all_args(vis = "...")]` overrides
+/// the visibility it inherits from the struct.
+///
+/// ```rust
+/// use lombokrs_codegen::{AllArgsConstructor, Getter};
+///
+/// #[derive(AllArgsConstructor, Getter, Debug)]
+/// pub struct User<'a> {
+///     id: u32,
+///     #[new(into)]
+///     name: String,
+///     email: &'a str,
+/// }
+///
+/// let user = User::new(10086, "photowey", "photowey@gmail.com");
+///
+/// assert_eq!(10086u32, user.get_id());
+/// assert_eq!("photowey", user.get_name());
+/// assert_eq!("photowey@gmail.com", user.get_email());
+/// ```
+#[proc_macro_derive(AllArgsConstructor, attributes(all_args, new))]
+pub fn all_args_constructor_derive(input: TokenStream) -> TokenStream {
+    derive_all_args_constructor(input)
+}
+
+/// `RequiredArgsConstructor` is a macro that generates a constructor taking the required fields only.
+///
+/// - new(id, name, ...) -> Self
+///
+/// A field is required unless it's an `Option`, a `Vec` or `#[builder(default)]`, the same rule
+/// [`Builder`] checks in `build()`. The other fields start out as `None`, empty, or their
+/// `#[builder(default = expr)]` / `#[default(expr)]`.
+///
+/// `#[new(into)]` and `#[required_args(name = "...", vis = "...")]` work like those of [`AllArgsConstructor`].
+///
+/// ```rust
+/// use lombokrs_codegen::{Getter, RequiredArgsConstructor};
+///
+/// #[derive(RequiredArgsConstructor, Getter, Debug)]
+/// pub struct Server {
+///     #[new(into)]
+///     host: String,
+///     #[builder(default = 8080)]
+///     port: u16,
+///     tags: Vec<String>,
+///     proxy: Option<String>,
+/// }
+///
+/// let server = Server::new("localhost");
+///
+/// assert_eq!("localhost", server.get_host());
+/// assert_eq!(8080u16, server.get_port());
+/// assert!(server.get_tags().is_empty());
+/// assert_eq!(None, server.get_proxy());
+/// ```
+#[proc_macro_derive(
+    RequiredArgsConstructor,
+    attributes(required_args, new, builder, default)
+)]
+pub fn required_args_constructor_derive(input: TokenStream) -> TokenStream {
+    derive_required_args_constructor(input)
+}
+
+/// `ToString` is a macro that implements `Display`, and thereby `to_string()`, in the Lombok style.
+///
+/// - `User(id=10086, name=photowey)`
+///
+/// `Option`s, collections, arrays and tuples print with `Debug`, the other fields with `Display`.
+/// `#[to_string(debug)]` and `#[to_string(debug = false)]` override that.
+///
+/// - `#[to_string(skip)]` / `#[to_string(only)]` select the fields like their `Getter` counterparts.
+/// - `#[to_string(rename = "...")]` prints a field under another name.
+/// - `#[to_string(include_field_names = false)]` prints `User(10086, photowey)`.
+/// - `#[to_string(format = "{name} <{email}>")]` replaces the whole output with a template,
+///   every `{field}` is checked against the fields at compile time. `{field:?}`, `{field:>8}`, ... keep their spec.
+///
+/// `#[sensitive]` fields are masked, see [`RedactedDebug`].
+///
+/// # Examples
+///
+/// ```rust
+/// use lombokrs_codegen::ToString;
+///
+/// #[derive(ToString)]
+/// pub struct User {
+///     id: u32,
+///     #[to_string(rename = "username")]
+///     name: String,
+///     hobby: Vec<String>,
+///     #[to_string(skip)]
+///     password: String,
+/// }
+///
+/// #[derive(ToString)]
+/// #[to_string(format = "{name} <{email}>")]
+/// pub struct Contact {
+///     name: String,
+///     email: String,
+/// }
+///
+/// // ----------------------------------------------------------------
+///
+/// // Usage:
+///
+///     let user = User {
+///         id: 10086,
+///         name: "photowey".to_string(),
+///         hobby: vec!["badminton".to_string()],
+///         password: "secret".to_string(),
+///     };
+///     assert_eq!(r#"User(id=10086, username=photowey, hobby=["badminton"])"#, user.to_string());
+///
+///     let contact = Contact {
+///         name: "photowey".to_string(),
+///         email: "photowey@gmail.com".to_string(),
+///     };
+///     assert_eq!("photowey <photowey@gmail.com>", contact.to_string());
+/// ```
+#[proc_macro_derive(ToString, attributes(to_string, sensitive))]
+pub fn to_string_derive(input: TokenStream) -> TokenStream {
+    derive_to_string(input)
+}
+
+/// `RedactedDebug` is a macro that implements `Debug` like `#[derive(Debug)]`, masking the `#[sensitive]` fields.
+///
+/// - `#[sensitive]` prints `***`.
+/// - `#[sensitive(last = 4)]` prints `***` and the last 4 characters of the `Display` output, e.g. `***1111`.
+/// - `#[sensitive(with = path::to::mask_fn)]` prints `mask_fn(&self.x)`, any `Display` value.
+///
+/// [`ToString`] masks the same fields. The errors of [`Builder`] name the missing fields only, never a value,
+/// and the `Debug` of `XxxBuilder` prints the `#[sensitive]` fields as `Some(***)` or `None`.
+///
+/// # Examples
+///
+/// ```rust
+/// use lombokrs_codegen::{RedactedDebug, ToString};
+///
+/// #[derive(RedactedDebug, ToString)]
+/// pub struct Payment {
+///     id: u32,
+///     #[sensitive(last = 4)]
+///     card: String,
+///     #[sensitive]
+///     cvv: String,
+/// }
+///
+/// // ----------------------------------------------------------------
+///
+/// // Usage:
+///
+///     let payment = Payment {
+///         id: 10086,
+///         card: "4111111111111111".to_string(),
+///         cvv: "123".to_string(),
+///     };
+///
+///     assert_eq!(r#"Payment { id: 10086, card: ***1111, cvv: *** }"#, format!("{:?}", payment));
+///     assert_eq!("Payment(id=10086, card=***1111, cvv=***)", payment.to_string());
+/// ```
+#[proc_macro_derive(RedactedDebug, attributes(sensitive))]
+pub fn redacted_debug_derive(input: TokenStream) -> TokenStream {
+    derive_redacted_debug(input)
+}
+
+/// `EqualsAndHashCode` is a macro that implements `PartialEq`, `Eq` and `Hash` over the same fields.
+///
+/// - `#[eq(skip)]` leaves out a field, e.g. a cache or a timestamp.
+/// - `#[eq(only)]` on any field leaves out every field without it.
+/// - `#[eq(id)]` on any field compares and hashes the `#[eq(id)]` fields only, the entity mode.
+///
+/// `Eq` and `Hash` apply whenever every selected field type implements them,
+/// a struct with an `f64` field still gets `PartialEq`.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashSet;
+///
+/// use lombokrs_codegen::EqualsAndHashCode;
+///
+/// #[derive(EqualsAndHashCode, Debug)]
+/// pub struct Article {
+///     title: String,
+///     #[eq(skip)]
+///     views: u64,
+/// }
+///
+/// #[derive(EqualsAndHashCode, Debug)]
+/// pub struct Order {
+///     #[eq(id)]
+///     id: u64,
+///     amount: f64,
+/// }
+///
+/// // ----------------------------------------------------------------
+///
+/// // Usage:
+///
+///     let article = Article { title: "lombok".to_string(), views: 1 };
+///     let viewed = Article { title: "lombok".to_string(), views: 2 };
+///     assert_eq!(article, viewed);
+///
+///     let articles: HashSet<Article> = [article, viewed].into_iter().collect();
+///     assert_eq!(1, articles.len());
+///
+///     assert_eq!(Order { id: 1, amount: 9.9 }, Order { id: 1, amount: 19.9 });
+///     assert_ne!(Order { id: 1, amount: 9.9 }, Order { id: 2, amount: 9.9 });
+/// ```
+#[proc_macro_derive(EqualsAndHashCode, attributes(eq))]
+pub fn equals_and_hash_code_derive(input: TokenStream) -> TokenStream {
+    derive_equals_and_hash_code(input)
+}
+
+/// `Value` is a composite macro for immutable value objects: [`Getter`], [`AllArgsConstructor`], [`With`],
+/// [`EqualsAndHashCode`] and [`ToString`], and no setters.
+///
+/// The getters and withers inherit the visibility of the struct, like the constructor.
+/// The helper attributes of every part apply, e.g. `#[getter(skip)]`, `#[new(into)]` or `#[eq(id)]`.
+///
+/// Deriving [`Setter`] or [`Data`] as well, or asking for `#[getter(mut)]`, is a compile error.
+/// Within the same `#[derive(..)]` attribute the derives can't see each other, the error is then
+/// E0592, duplicate definitions with name `Value_cannot_be_combined_with_Setter_or_Data`.
+/// `Value` implements `PartialEq`, `Eq`, `Hash` and `Display`, don't derive them again.
+///
+/// # Examples
+///
+/// ```rust
+/// use lombokrs_codegen::Value;
+///
+/// #[derive(Value, Clone, Debug)]
+/// pub struct Address {
+///     #[new(into)]
+///     city: String,
+///     zip: u32,
+/// }
+///
+/// // ----------------------------------------------------------------
+///
+/// // Usage:
+///
+///     let address = Address::new("Chengdu", 610000);
+///     assert_eq!("Chengdu", address.get_city());
+///
+///     let moved = address.with_zip_cloned(610041);
+///     assert_ne!(address, moved);
+///     assert_eq!("Address(city=Chengdu, zip=610041)", moved.to_string());
+/// ```
+///
+/// ```rust,compile_fail
+/// use lombokrs_codegen::{Setter, Value};
+///
+/// #[derive(Value, Setter)]
+/// pub struct Address {
+///     city: String,
+/// }
+/// ```
+#[proc_macro_derive(
+    Value,
+    attributes(getter, all_args, new, with, eq, to_string, sensitive)
+)]
+pub fn value_derive(input: TokenStream) -> TokenStream {
+    derive_value(input)
+}
+
 /// `Data` is a composite macro that includes [`Setter`], [`Getter`], and [`Builder`].
-#[proc_macro_derive(Data)]
+///
+/// The helper attributes of every part apply, e.g. `#[getter(vis = "pub")]`, `#[setter(chain)]`
+/// or `#[builder(method = "activity")]`. `#[data(getter = false, setter = false, builder = false)]`
+/// turns the parts off.
+///
+/// # Examples
+///
+/// ```rust
+/// use lombokrs_codegen::Data;
+///
+/// #[derive(Data, Debug)]
+/// #[data(builder = false)]
+/// #[getter(vis = "pub")]
+/// #[setter(chain, vis = "pub")]
+/// pub struct Profile {
+///     name: String,
+///     #[getter(skip)]
+///     #[setter(collection = "tag")]
+///     tags: Vec<String>,
+/// }
+///
+/// #[derive(Data, Debug)]
+/// pub struct User {
+///     id: u32,
+///     #[builder(method = "activity")]
+///     activities: Vec<String>,
+/// }
+///
+/// // ----------------------------------------------------------------
+///
+/// // Usage:
+///
+///     let mut profile = Profile { name: "photowey".to_string(), tags: Vec::new() };
+///     profile.set_name("lombok".to_string()).add_tag("rust".to_string());
+///     assert_eq!("lombok", profile.get_name());
+///
+///     let user = User::builder().id(10086).activity("badminton".to_string()).build().unwrap();
+///     assert_eq!(&vec!["badminton".to_string()], user.get_activities());
+/// ```
+#[proc_macro_derive(Data, attributes(data, setter, getter, builder, default))]
 pub fn data_derive(input: TokenStream) -> TokenStream {
-    TokenStream::from_iter(
-        vec![
-            derive_setter(input.clone()),
-            derive_getter(input.clone()),
-            derive_builder(input.clone()),
-        ]
-        .into_iter(),
-    )
+    derive_data(input)
+}
+
+/// `track_changes` is an attribute macro that records which fields changed through the [`Setter`] setters.
+///
+/// It adds a hidden `__lombok_changes` bit set to the struct, and the struct gains:
+///
+/// - changed_fields() -> impl Iterator<Item = &'static str>
+/// - is_dirty() -> bool
+/// - reset_changes()
+///
+/// Every `&mut self` setter marks its field, including `clear_x()`, `replace_x(..)` and the collection methods.
+/// `x_mut()` getters don't. `#[setter(interior)]` fields are a compile error, their `&self` setters can't mark.
+///
+/// With [`history`], `undo()`, `redo()` and `rollback_to(..)` put back the state the field had before
+/// the change: undoing the only change of a field makes it clean again. A field whose change was
+/// recorded before the last `reset_changes()` is marked instead, it may differ from the reset state.
+///
+/// Place it above `#[derive(..)]` so the derives see the hidden field. [`Builder`] initializes it,
+/// the accessors skip it. The bit set derives `Clone`, `Copy`, `Default` and `Debug`, and is always
+/// equal and hashes nothing, so a derived `PartialEq` or `Hash` ignores the dirty state.
+///
+/// ```rust
+/// use lombokrs_codegen::{track_changes, Builder, Getter, Setter};
+///
+/// #[track_changes]
+/// #[derive(Setter, Getter, Builder, Debug)]
+/// pub struct Row {
+///     id: u32,
+///     name: String,
+///     email: String,
+/// }
+///
+/// let mut row = Row::builder()
+///     .id(1)
+///     .name("photowey".to_string())
+///     .email("photowey@gmail.com".to_string())
+///     .build()
+///     .unwrap();
+///
+/// assert!(!row.is_dirty());
+///
+/// row.set_email("photowey@github.com".to_string());
+///
+/// assert!(row.is_dirty());
+/// assert_eq!(vec!["email"], row.changed_fields().collect::<Vec<_>>());
+///
+/// row.reset_changes();
+/// assert!(!row.is_dirty());
+/// ```
+///
+/// ```rust,compile_fail
+/// use std::cell::Cell;
+///
+/// use lombokrs_codegen::{track_changes, Setter};
+///
+/// #[track_changes]
+/// #[derive(Setter)]
+/// pub struct Counter {
+///     #[setter(interior)]
+///     hits: Cell<u32>,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn track_changes(args: TokenStream, input: TokenStream) -> TokenStream {
+    derive_track_changes(args, input)
+}
+
+/// `change_listeners` is an attribute macro that adds a registry of change listeners to the [`Setter`] setters.
+///
+/// It adds a hidden `__lombok_listeners` field and `#[setter(notify)]` to the struct, which gains:
+///
+/// - add_change_listener(impl Fn(&'static str, &dyn Any, &dyn Any))
+/// - clear_change_listeners()
+///
+/// Every setter calls the listeners with `(field, &old, &new)` after the assignment, downcast the values
+/// with `Any`, so the field types must be `'static`. `#[setter(notify = false)]` opts a field out.
+/// A struct-level `#[setter(notify)]` hook can't be combined with it.
+///
+/// Place it above `#[derive(..)]` so the derives see the hidden field. [`Builder`] initializes it,
+/// the accessors skip it. A clone shares the listeners, a derived `PartialEq` or `Hash` ignores them.
+///
+/// ```rust
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// use lombokrs_codegen::{change_listeners, Getter, Setter};
+///
+/// #[change_listeners]
+/// #[derive(Setter, Getter, Debug, Default)]
+/// pub struct Form {
+///     title: String,
+///     #[setter(collection = "field")]
+///     fields: Vec<String>,
+/// }
+///
+/// let renders = Rc::new(RefCell::new(Vec::new()));
+///
+/// let mut form = Form::default();
+/// let listener_renders = Rc::clone(&renders);
+/// form.add_change_listener(move |field, _, new| {
+///     if let Some(new) = new.downcast_ref::<String>() {
+///         listener_renders.borrow_mut().push(format!("{}: {}", field, new));
+///     }
+///     if let Some(new) = new.downcast_ref::<Vec<String>>() {
+///         listener_renders.borrow_mut().push(format!("{}: {:?}", field, new));
+///     }
+/// });
+///
+/// form.set_title("signup".to_string());
+/// form.add_field("email".to_string());
+///
+/// assert_eq!(vec!["title: signup", r#"fields: ["email"]"#], *renders.borrow());
+/// ```
+#[proc_macro_attribute]
+pub fn change_listeners(args: TokenStream, input: TokenStream) -> TokenStream {
+    derive_change_listeners(args, input)
+}
+
+/// `history` is an attribute macro that keeps a bounded undo/redo history of the [`Setter`] setters.
+///
+/// Every `&mut self` setter records the previous value, the struct gains:
+///
+/// - undo() -> bool
+/// - redo() -> bool
+/// - history_len() -> usize
+/// - checkpoint() -> u64
+/// - rollback_to(checkpoint) -> bool
+///
+/// `#[history(capacity = N)]` keeps the latest `N` changes, 64 by default. A new change drops the undone ones.
+/// `take_x()`, `replace_x(..)`, `update_x(..)` and the collection methods record a clone, they need `T: Clone`.
+/// The collection methods clone the whole collection on every call, e.g. `add_x(..)` on a `Vec` of `n`
+/// elements costs `O(n)`: prefer `set_x(..)`/`extend_xs(..)` for bulk changes on large collections.
+/// `x_mut()` getters aren't recorded. `#[setter(interior)]` fields are a compile error, their `&self`
+/// setters can't record.
+///
+/// `undo()` and `redo()` write the fields like a setter: the `#[setter(notify)]` hook and the
+/// [`change_listeners`] are called with `(field, &old, &new)`.
+///
+/// Place it above `#[derive(..)]` so the derives see the hidden history field. [`Builder`] initializes it,
+/// the accessors skip it. The history is cloned with the struct, and is always equal and hashes nothing,
+/// so a derived `PartialEq` or `Hash` ignores it.
+///
+/// ```rust
+/// use lombokrs_codegen::{history, Builder, Getter, Setter};
+///
+/// #[history(capacity = 16)]
+/// #[derive(Setter, Getter, Builder, Debug)]
+/// pub struct Canvas {
+///     color: String,
+///     width: u32,
+/// }
+///
+/// let mut canvas = Canvas::builder().color("white".to_string()).width(1).build().unwrap();
+/// let origin = canvas.checkpoint();
+///
+/// canvas.set_color("black".to_string());
+/// canvas.set_width(2);
+/// canvas.set_width(3);
+/// assert_eq!(3, canvas.history_len());
+///
+/// assert!(canvas.undo());
+/// assert_eq!(2u32, canvas.width());
+/// assert!(canvas.redo());
+/// assert_eq!(3u32, canvas.width());
+///
+/// assert!(canvas.rollback_to(origin));
+/// assert_eq!("white", canvas.color());
+/// assert_eq!(1u32, canvas.width());
+/// ```
+///
+/// ```rust,compile_fail
+/// use std::cell::Cell;
+///
+/// use lombokrs_codegen::{history, Setter};
+///
+/// #[history]
+/// #[derive(Setter)]
+/// pub struct Counter {
+///     #[setter(interior)]
+///     hits: Cell<u32>,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn history(args: TokenStream, input: TokenStream) -> TokenStream {
+    derive_history(args, input)
 }
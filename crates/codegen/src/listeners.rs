@@ -0,0 +1,141 @@
+/*
+ * Copyright © 2024 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// listeners
+
+// ----------------------------------------------------------------
+
+use proc_macro::TokenStream;
+
+use quote::{format_ident, quote};
+use syn::parse::Parser;
+use syn::{parse_quote, Data, DataStruct, DeriveInput, Field, Fields, Generics};
+
+use crate::attribute::{derive_hidden_field_impls, AttributeArgs};
+use crate::setter::{SETTER_ATTR_NAME, SETTER_ATTR_NOTIFY, SETTER_KEYS};
+
+// ----------------------------------------------------------------
+
+/// The hidden registry of the change listeners.
+const LISTENERS_FIELD: &str = "__lombok_listeners";
+/// The `#[setter(notify)]` hook calling the registered listeners.
+const LISTENERS_HOOK: &str = "__lombok_notify_listeners";
+
+// ----------------------------------------------------------------
+
+pub(crate) fn derive_change_listeners(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = proc_macro2::TokenStream::from(args);
+    if !args.is_empty() {
+        let err = syn::Error::new_spanned(args, "expected `#[change_listeners]`");
+        return TokenStream::from(err.to_compile_error());
+    }
+
+    let derive_input = syn::parse_macro_input!(input as DeriveInput);
+
+    match try_derive_change_listeners(derive_input) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+fn try_derive_change_listeners(
+    mut derive_input: DeriveInput,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let struct_args = AttributeArgs::try_parse(SETTER_ATTR_NAME, SETTER_KEYS, &derive_input.attrs)?;
+    if let Some(notify) = struct_args.get(SETTER_ATTR_NOTIFY) {
+        return Err(syn::Error::new_spanned(
+            &notify.key,
+            "`#[change_listeners]` notifies the listeners, remove the struct-level `notify`",
+        ));
+    }
+
+    let listeners_name = format_ident!("__Lombok{}Listeners", derive_input.ident);
+    let Data::Struct(DataStruct {
+        fields: Fields::Named(named_fields),
+        ..
+    }) = &mut derive_input.data
+    else {
+        return Err(syn::Error::new_spanned(
+            &derive_input.ident,
+            "`#[change_listeners]` expects a struct with named fields",
+        ));
+    };
+
+    // __lombok_listeners: __LombokXListeners
+    let listeners = format_ident!("{}", LISTENERS_FIELD);
+    let listeners_field = Field::parse_named.parse2(quote! {
+        #[doc(hidden)]
+        #listeners: #listeners_name
+    })?;
+    named_fields.named.push(listeners_field);
+
+    let hook = format_ident!("{}", LISTENERS_HOOK);
+    derive_input
+        .attrs
+        .push(parse_quote!(#[setter(notify = #hook)]));
+
+    let struct_name = &derive_input.ident;
+    let vis = &derive_input.vis;
+    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+
+    let listeners_impls = derive_hidden_field_impls(&listeners_name, &Generics::default());
+
+    let expanded = quote! {
+        #derive_input
+
+        /// The registered change listeners, see `#[change_listeners]`.
+        #[doc(hidden)]
+        #[derive(Clone, Default)]
+        struct #listeners_name(
+            ::std::vec::Vec<
+                ::std::rc::Rc<dyn ::std::ops::Fn(&'static str, &dyn ::std::any::Any, &dyn ::std::any::Any)>,
+            >,
+        );
+
+        #listeners_impls
+
+        impl ::std::fmt::Debug for #listeners_name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.debug_struct(stringify!(#listeners_name))
+                    .field("listeners", &self.0.len())
+                    .finish()
+            }
+        }
+
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            /// Register a listener called with `(field, &old, &new)` after every setter.
+            #vis fn add_change_listener(
+                &mut self,
+                listener: impl ::std::ops::Fn(&'static str, &dyn ::std::any::Any, &dyn ::std::any::Any) + 'static,
+            ) {
+                self.#listeners.0.push(::std::rc::Rc::new(listener));
+            }
+
+            /// Remove every registered listener.
+            #vis fn clear_change_listeners(&mut self) {
+                self.#listeners.0.clear();
+            }
+
+            fn #hook<V: ::std::any::Any>(&self, field: &'static str, old: &V, new: &V) {
+                for listener in self.#listeners.0.iter() {
+                    listener(field, old, new);
+                }
+            }
+        }
+    };
+
+    Ok(expanded)
+}
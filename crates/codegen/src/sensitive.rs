@@ -0,0 +1,178 @@
+/*
+ * Copyright © 2024 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// sensitive
+
+// ----------------------------------------------------------------
+
+use proc_macro::TokenStream;
+
+use quote::{quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::{parse_quote, DeriveInput, Expr, ExprLit, Field, Ident, Lit, WherePredicate};
+use synext::{try_derive_input, try_parse_named_fields};
+
+use crate::attribute::{try_predicate_is_hidden_field, ArgShape, AttributeArgs, AttributeKey};
+
+// ----------------------------------------------------------------
+
+pub(crate) const ATTR_SENSITIVE: &str = "sensitive";
+
+const SENSITIVE_ATTR_LAST: &str = "last";
+const SENSITIVE_ATTR_WITH: &str = "with";
+
+const SENSITIVE_KEYS: &[&[AttributeKey]] = &[&[
+    (SENSITIVE_ATTR_LAST, ArgShape::Expr),
+    (SENSITIVE_ATTR_WITH, ArgShape::Expr),
+]];
+
+const MASK: &str = "***";
+
+// ----------------------------------------------------------------
+
+/// A `#[sensitive]` field, printed masked by `ToString` and `RedactedDebug`.
+pub(crate) enum Sensitive {
+    /// `#[sensitive]` -> `***`
+    Full,
+    /// `#[sensitive(last = 4)]` -> `***1234`, the last characters of the `Display` output.
+    Last(usize),
+    /// `#[sensitive(with = path::to::mask_fn)]` -> `mask_fn(&self.x)`, any `Display` value.
+    With(proc_macro2::TokenStream),
+}
+
+impl Sensitive {
+    /// Try to parse the `#[sensitive(..)]` attribute of a field.
+    pub(crate) fn try_parse(field: &Field) -> syn::Result<Option<Self>> {
+        if !field
+            .attrs
+            .iter()
+            .any(|attr| attr.path.is_ident(ATTR_SENSITIVE))
+        {
+            return Ok(None);
+        }
+
+        let field_args = AttributeArgs::try_parse(ATTR_SENSITIVE, SENSITIVE_KEYS, &field.attrs)?;
+        if let Some(mask) = field_args.try_expr(SENSITIVE_ATTR_WITH)? {
+            return Ok(Some(Self::With(mask.to_token_stream())));
+        }
+
+        match field_args.try_expr(SENSITIVE_ATTR_LAST)? {
+            None => Ok(Some(Self::Full)),
+            Some(Expr::Lit(ExprLit {
+                lit: Lit::Int(last),
+                ..
+            })) => Ok(Some(Self::Last(last.base10_parse()?))),
+            Some(last) => Err(syn::Error::new_spanned(last, "expected `last = N`")),
+        }
+    }
+
+    /// The masked value of `self.#field_name`, a `Display` value.
+    pub(crate) fn derive_masked(&self, field_name: &Ident) -> proc_macro2::TokenStream {
+        match self {
+            Self::Full => quote! { #MASK },
+            Self::Last(last) => quote! {
+                {
+                    let value = ::std::string::ToString::to_string(&self.#field_name);
+                    let chars = value.chars().count();
+                    if chars > #last {
+                        let tail: ::std::string::String = value.chars().skip(chars - #last).collect();
+                        ::std::format!("{}{}", #MASK, tail)
+                    } else {
+                        ::std::string::ToString::to_string(#MASK)
+                    }
+                }
+            },
+            Self::With(mask) => quote! { #mask(&self.#field_name) },
+        }
+    }
+
+    /// The bound the masked field type needs in a generic struct.
+    pub(crate) fn derive_bound(&self, field: &Field) -> Option<WherePredicate> {
+        let field_type = &field.ty;
+
+        match self {
+            Self::Last(_) => Some(parse_quote!(#field_type: ::std::fmt::Display)),
+            _ => None,
+        }
+    }
+}
+
+// ----------------------------------------------------------------
+
+pub(crate) fn derive_redacted_debug(input: TokenStream) -> TokenStream {
+    let derive_input = try_derive_input(input);
+
+    match try_derive_redacted_debug(&derive_input) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+/// `Debug` like `#[derive(Debug)]`, masking the `#[sensitive]` fields and leaving out the hidden fields.
+fn try_derive_redacted_debug(derive_input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let struct_name = &derive_input.ident;
+
+    let mut entries = Vec::new();
+    let mut bounds: Vec<WherePredicate> = Vec::new();
+
+    let fields = try_parse_named_fields(derive_input);
+    for field in fields.iter() {
+        if try_predicate_is_hidden_field(field) {
+            continue;
+        }
+
+        let field_name = field.ident.as_ref().unwrap();
+        let label = field_name.unraw().to_string();
+
+        match Sensitive::try_parse(field)? {
+            Some(sensitive) => {
+                let masked = sensitive.derive_masked(field_name);
+                entries.push(quote! {
+                    .field(#label, &::std::format_args!("{}", #masked))
+                });
+                bounds.extend(sensitive.derive_bound(field));
+            }
+            None => {
+                let field_type = &field.ty;
+                entries.push(quote! {
+                    .field(#label, &self.#field_name)
+                });
+                bounds.push(parse_quote!(#field_type: ::std::fmt::Debug));
+            }
+        }
+    }
+
+    let mut generics = derive_input.generics.clone();
+    if generics.type_params().next().is_some() {
+        generics.make_where_clause().predicates.extend(bounds);
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let name = struct_name.unraw().to_string();
+    let expanded = quote! {
+        impl #impl_generics ::std::fmt::Debug for #struct_name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.debug_struct(#name)
+                    #(
+                        #entries
+                    )*
+                    .finish()
+            }
+        }
+    };
+
+    Ok(expanded)
+}
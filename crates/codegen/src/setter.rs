@@ -21,41 +21,433 @@
 use proc_macro::TokenStream;
 
 use quote::{format_ident, quote};
-use synext::{try_derive_input, try_parse_named_fields};
+use syn::{DeriveInput, Expr, ExprLit, Field, Ident, Lit, Type, Visibility};
+use synext::{
+    try_derive_input, try_parse_named_fields, try_predicate_is_option, try_unwrap_option,
+};
+
+use crate::attribute::{
+    try_predicate_is_hidden_field, try_select_fields, ArgShape, AttributeArg, AttributeArgs,
+    AttributeKey, SELECTION_KEYS, VIS_KEYS,
+};
+use crate::builder::try_parse_builder_method;
+use crate::history::{derive_phantom_arm, derive_record, derive_record_name, ATTR_HISTORY};
+use crate::interior::{Interior, InteriorKind, INTERIOR_KEYS};
+use crate::track::{
+    derive_changed_state, derive_mark_changed, derive_restore_changed, ATTR_TRACK_CHANGES,
+};
+use crate::types::{try_extract_type_arguments, try_predicate_collection_kind, CollectionKind};
+use crate::value::derive_immutable_marker;
 
 // ----------------------------------------------------------------
 
 const SETTER_PREFIX: &str = "set_";
+const CLEAR_PREFIX: &str = "clear_";
+const OPTION_TAKE_PREFIX: &str = "take_";
+const COLLECTION_ADD_PREFIX: &str = "add_";
+const COLLECTION_EXTEND_PREFIX: &str = "extend_";
+const COLLECTION_REMOVE_PREFIX: &str = "remove_";
+const COLLECTION_REMOVE_AT_SUFFIX: &str = "_at";
+const MAP_INSERT_PREFIX: &str = "insert_";
+const REPLACE_PREFIX: &str = "replace_";
+const UPDATE_PREFIX: &str = "update_";
+
+pub(crate) const SETTER_ATTR_NAME: &str = "setter";
+const SETTER_ATTR_CHAIN: &str = "chain";
+const SETTER_ATTR_FLUENT: &str = "fluent";
+const SETTER_ATTR_COLLECTION: &str = "collection";
+const SETTER_ATTR_REPLACE: &str = "replace";
+const SETTER_ATTR_UPDATE: &str = "update";
+pub(crate) const SETTER_ATTR_NOTIFY: &str = "notify";
+
+/// Every key of `#[setter(..)]`, including the ones added by `#[track_changes]` and `#[history]`.
+pub(crate) const SETTER_KEYS: &[&[AttributeKey]] = &[
+    SELECTION_KEYS,
+    VIS_KEYS,
+    INTERIOR_KEYS,
+    &[
+        (SETTER_ATTR_CHAIN, ArgShape::Flag),
+        (SETTER_ATTR_FLUENT, ArgShape::Flag),
+        (SETTER_ATTR_COLLECTION, ArgShape::FlagOrStr),
+        (SETTER_ATTR_REPLACE, ArgShape::Flag),
+        (SETTER_ATTR_UPDATE, ArgShape::Flag),
+        (SETTER_ATTR_NOTIFY, ArgShape::FlagOrExpr),
+        (ATTR_TRACK_CHANGES, ArgShape::Flag),
+        (ATTR_HISTORY, ArgShape::Flag),
+    ],
+];
+
+/// The hook called by `#[setter(notify)]`.
+const NOTIFY_DEFAULT_HOOK: &str = "on_change";
+
+// ----------------------------------------------------------------
+
+/// The per-field options shared by every generated setter.
+struct SetterContext<'a> {
+    vis: Visibility,
+    field_name: &'a Ident,
+    field_type: &'a Type,
+    /// `#[setter(chain)]`: return `&mut Self` instead of `()`.
+    chain: bool,
+    /// `#[setter(fluent)]`: x(..) instead of set_x(..).
+    fluent: bool,
+    /// `#[track_changes]`: the bit of the field.
+    track: Option<usize>,
+    /// `#[setter(notify = on_change)]`: the hook called with `(field, &old, &new)`.
+    notify: Option<Ident>,
+    /// `#[history]`: the struct whose history records the previous value.
+    history: Option<&'a Ident>,
+}
+
+impl SetterContext<'_> {
+    /// set_x(..) or x(..)
+    fn setter_name(&self) -> Ident {
+        if self.fluent {
+            self.field_name.clone()
+        } else {
+            format_ident!("{}{}", SETTER_PREFIX, self.field_name)
+        }
+    }
+
+    /// Mark the field as changed with `#[track_changes]`.
+    fn derive_mark(&self) -> proc_macro2::TokenStream {
+        match self.track {
+            Some(index) => derive_mark_changed(index),
+            None => quote! {},
+        }
+    }
+
+    /// `#[track_changes]`: the state of the field before the change, kept by `#[history]`.
+    fn derive_changed_state(&self) -> proc_macro2::TokenStream {
+        match self.track {
+            Some(index) => derive_changed_state(index),
+            None => quote! { 0 },
+        }
+    }
+
+    /// Record the previous value `old` with `#[history]`, before the field is marked.
+    fn derive_record(&self, old: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match self.history {
+            Some(struct_name) => derive_record(
+                struct_name,
+                self.field_name,
+                old,
+                self.derive_changed_state(),
+            ),
+            None => quote! {},
+        }
+    }
+
+    /// Whether the `#[setter(notify)]` hook or `#[history]` needs the previous value.
+    fn is_observed(&self) -> bool {
+        self.notify.is_some() || self.history.is_some()
+    }
+
+    /// Call the `#[setter(notify)]` hook after the previous value was moved into `old`.
+    fn derive_notify(&self, new: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match &self.notify {
+            Some(hook) => {
+                let field = self.field_name.to_string();
+                quote! { self.#hook(#field, &old, #new); }
+            }
+            None => quote! {},
+        }
+    }
+
+    /// `self.x = value;`, or `let old = swap;` for the `#[setter(notify)]` hook and `#[history]`.
+    fn derive_assign(
+        &self,
+        value: proc_macro2::TokenStream,
+        swap: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let field_name = self.field_name;
+
+        if self.is_observed() {
+            let notify = self.derive_notify(quote! { &self.#field_name });
+            let record = self.derive_record(quote! { old });
+            quote! {
+                let old = #swap;
+                #notify
+                #record
+            }
+        } else {
+            quote! { self.#field_name = #value; }
+        }
+    }
+
+    /// `let old = swap;`, keeping `old` for the caller of `take_x()` or `replace_x(..)`.
+    ///
+    /// `#[history]` records a clone of `old`, see [`SetterContext::derive_clone_bound`].
+    fn derive_swap(&self, swap: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let field_name = self.field_name;
+        let notify = self.derive_notify(quote! { &self.#field_name });
+        let record = self.derive_record(quote! { ::std::clone::Clone::clone(&old) });
+
+        quote! {
+            let old = #swap;
+            #notify
+            #record
+        }
+    }
+
+    /// The in-place `body`, e.g. `self.x.push(..)`, with a clone of the field in `old`
+    /// for the `#[setter(notify)]` hook and `#[history]`.
+    fn derive_modify(&self, body: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let field_name = self.field_name;
+        if !self.is_observed() {
+            return body;
+        }
+
+        let notify = self.derive_notify(quote! { &self.#field_name });
+        let record = self.derive_record(quote! { old });
+
+        quote! {
+            let old = ::std::clone::Clone::clone(&self.#field_name);
+            #body
+            #notify
+            #record
+        }
+    }
+
+    /// `where T: Clone` for the setters that clone a `cloned` value, deferred to the call site:
+    /// the other setters of a field that isn't `Clone` still work.
+    fn derive_clone_bound(&self, value_type: &Type, cloned: bool) -> proc_macro2::TokenStream {
+        if cloned {
+            quote! { where for<'__lombok> #value_type: ::std::clone::Clone }
+        } else {
+            quote! {}
+        }
+    }
+
+    /// A `&mut self` setter returning `()`, or `&mut Self` with `#[setter(chain)]`.
+    fn derive_mutator(
+        &self,
+        name: &Ident,
+        arguments: proc_macro2::TokenStream,
+        body: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        self.derive_bounded_mutator(name, arguments, quote! {}, body)
+    }
+
+    /// A [`SetterContext::derive_mutator`] modifying the field in place, see [`SetterContext::derive_modify`].
+    fn derive_modifier(
+        &self,
+        name: &Ident,
+        arguments: proc_macro2::TokenStream,
+        body: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        self.derive_bounded_mutator(
+            name,
+            arguments,
+            self.derive_clone_bound(self.field_type, self.is_observed()),
+            self.derive_modify(body),
+        )
+    }
+
+    fn derive_bounded_mutator(
+        &self,
+        name: &Ident,
+        arguments: proc_macro2::TokenStream,
+        bound: proc_macro2::TokenStream,
+        body: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let vis = &self.vis;
+        let mark = self.derive_mark();
+
+        if self.chain {
+            quote! {
+                #vis fn #name(&mut self, #arguments) -> &mut Self #bound {
+                    #body
+                    #mark
+                    self
+                }
+            }
+        } else {
+            quote! {
+                #vis fn #name(&mut self, #arguments) #bound {
+                    #body
+                    #mark
+                }
+            }
+        }
+    }
+
+    /// Like [`SetterContext::derive_mutator`], through `&self` for interior-mutable fields.
+    fn derive_shared_mutator(
+        &self,
+        name: &Ident,
+        arguments: proc_macro2::TokenStream,
+        bound: proc_macro2::TokenStream,
+        body: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let vis = &self.vis;
+
+        if self.chain {
+            quote! {
+                #vis fn #name(&self, #arguments) -> &Self #bound {
+                    #body
+                    self
+                }
+            }
+        } else {
+            quote! {
+                #vis fn #name(&self, #arguments) #bound {
+                    #body
+                }
+            }
+        }
+    }
+}
 
 // ----------------------------------------------------------------
 
 pub(crate) fn derive_setter(input: TokenStream) -> TokenStream {
     let derive_input = try_derive_input(input);
+
+    match try_derive_setters(&derive_input) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+fn try_derive_setters(derive_input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let struct_name = &derive_input.ident;
 
     let (impl_generics, ty_generics, where_clause) = &derive_input.generics.split_for_impl();
 
+    let struct_args = AttributeArgs::try_parse(SETTER_ATTR_NAME, SETTER_KEYS, &derive_input.attrs)?;
+
     let mut setters = Vec::new();
+    let mut swap_arms = Vec::new();
+
+    let fields = try_parse_named_fields(derive_input);
+
+    // #[track_changes]: one bit per field, in declaration order.
+    let tracked_fields: Vec<&Ident> = match struct_args.try_flag(ATTR_TRACK_CHANGES)? {
+        Some(true) => fields
+            .iter()
+            .filter(|field| !try_predicate_is_hidden_field(field))
+            .map(|field| field.ident.as_ref().unwrap())
+            .collect(),
+        _ => Vec::new(),
+    };
 
-    let fields = try_parse_named_fields(&derive_input);
-    fields.iter().for_each(|field| {
-        let field_name = field.ident.clone().unwrap();
+    for (field, field_args) in try_select_fields(SETTER_ATTR_NAME, SETTER_KEYS, fields)? {
+        let field_name = field.ident.as_ref().unwrap();
         let field_type = &field.ty;
 
+        let context = SetterContext {
+            vis: field_args.try_vis_or(&struct_args, &field.vis)?,
+            field_name,
+            field_type,
+            chain: field_args.try_flag_or(&struct_args, SETTER_ATTR_CHAIN)?,
+            fluent: field_args.try_flag_or(&struct_args, SETTER_ATTR_FLUENT)?,
+            track: tracked_fields
+                .iter()
+                .position(|tracked_field| *tracked_field == field_name),
+            notify: try_parse_notify(&field_args, &struct_args)?,
+            history: struct_args
+                .try_flag(ATTR_HISTORY)?
+                .unwrap_or_default()
+                .then_some(struct_name),
+        };
+
+        let replace = field_args.try_flag_or(&struct_args, SETTER_ATTR_REPLACE)?;
+        let update = field_args.try_flag_or(&struct_args, SETTER_ATTR_UPDATE)?;
+
+        // `Cell<T>`, `Mutex<T>`, `AtomicBool`, ...: write `T` through the wrapper.
+        if let Some(interior) = Interior::try_parse(&field_args, &struct_args, field_type)? {
+            if context.track.is_some() || context.history.is_some() {
+                return Err(syn::Error::new_spanned(
+                    field_name,
+                    "`#[track_changes]` and `#[history]` can't follow the `&self` setters of an interior-mutable field, add `#[setter(interior = false)]`",
+                ));
+            }
+            if update
+                && interior.kind == InteriorKind::Atomic
+                && field_args.try_flag(SETTER_ATTR_UPDATE)?.is_some()
+            {
+                return Err(syn::Error::new_spanned(
+                    field_type,
+                    "`#[setter(update)]` isn't supported on atomic fields, use `fetch_update`",
+                ));
+            }
+
+            setters.extend(derive_interior_setters(
+                &context, &interior, replace, update,
+            ));
+            continue;
+        }
+
+        if context.history.is_some() {
+            swap_arms.push(derive_swap_arm(&context));
+        }
+
+        if replace {
+            setters.push(derive_replace_setter(&context));
+        }
+        if update {
+            setters.push(derive_update_setter(&context));
+        }
+
+        if try_predicate_is_option(field_type) {
+            setters.extend(derive_option_setters(&context));
+            continue;
+        }
+
+        if let Some(element_name) = try_parse_element_name(field, &field_args)? {
+            let kind = try_predicate_collection_kind(field_type).ok_or_else(|| {
+                syn::Error::new_spanned(
+                    field_type,
+                    "`#[setter(collection)]` expects a `Vec`, `VecDeque`, `HashSet`, `BTreeSet`, `HashMap` or `BTreeMap` field",
+                )
+            })?;
+            setters.extend(derive_collection_setters(&context, kind, &element_name));
+        }
+
         // set_x(..)
         // |- set_id(..)
         // |- set_name(..)
         // |- ...
-        let setter_name = format_ident!("{}{}", SETTER_PREFIX, field_name);
+        let setter = context.derive_mutator(
+            &context.setter_name(),
+            quote! { #field_name: #field_type },
+            context.derive_assign(
+                quote! { #field_name },
+                quote! { ::std::mem::replace(&mut self.#field_name, #field_name) },
+            ),
+        );
 
-        let setter = quote! {
-            pub fn #setter_name(&mut self, #field_name: #field_type) {
-                self.#field_name = #field_name;
-            }
+        setters.push(setter);
+    }
+
+    // #[history]: undo()/redo() swap the records in like a setter, notifying and marking the field.
+    if struct_args.try_flag(ATTR_HISTORY)?.unwrap_or_default() {
+        let record_name = derive_record_name(struct_name);
+        let phantom_arm = derive_phantom_arm(struct_name, &derive_input.generics);
+        let restored = if tracked_fields.is_empty() {
+            format_ident!("_restored")
+        } else {
+            format_ident!("restored")
         };
+        setters.push(quote! {
+            fn __lombok_swap_record(
+                &mut self,
+                record: #record_name #ty_generics,
+                #restored: u64,
+            ) -> (#record_name #ty_generics, u64) {
+                match record {
+                    #(
+                        #swap_arms
+                    )*
+                    #phantom_arm
+                }
+            }
+        });
+    }
 
-        setters.push(setter);
-    });
+    // #[derive(Value, Setter)]
+    let immutable_marker = derive_immutable_marker(derive_input);
 
     let expanded = quote! {
         impl #impl_generics #struct_name #ty_generics #where_clause {
@@ -63,10 +455,494 @@ pub(crate) fn derive_setter(input: TokenStream) -> TokenStream {
                 #setters
             )*
         }
+
+        #immutable_marker
+    };
+
+    Ok(expanded)
+}
+
+/// `#[history]`: the arm of `__lombok_swap_record(..)` putting a recorded value back.
+///
+/// Returns the value it replaces and the `#[track_changes]` state of the field, for `redo()`/`undo()`.
+fn derive_swap_arm(context: &SetterContext) -> proc_macro2::TokenStream {
+    let SetterContext {
+        field_name,
+        history,
+        ..
+    } = context;
+    let record_name = derive_record_name(history.unwrap());
+    let state = context.derive_changed_state();
+    let restore = match context.track {
+        Some(index) => derive_restore_changed(index, quote! { restored }),
+        None => quote! {},
+    };
+    let notify = context.derive_notify(quote! { &self.#field_name });
+
+    quote! {
+        #record_name::#field_name(value) => {
+            let state = #state;
+            let old = ::std::mem::replace(&mut self.#field_name, value);
+            #restore
+            #notify
+            (#record_name::#field_name(old), state)
+        }
+    }
+}
+
+/// `Option<T>`
+///
+/// - set_x(T)
+/// - clear_x()
+/// - take_x() -> Option<T>
+fn derive_option_setters(context: &SetterContext) -> Vec<proc_macro2::TokenStream> {
+    let SetterContext {
+        vis,
+        field_name,
+        field_type,
+        ..
+    } = context;
+    let inner_type = try_unwrap_option(field_type);
+    let mark = context.derive_mark();
+
+    let setter = context.derive_mutator(
+        &context.setter_name(),
+        quote! { #field_name: #inner_type },
+        context.derive_assign(
+            quote! { ::std::option::Option::Some(#field_name) },
+            quote! { self.#field_name.replace(#field_name) },
+        ),
+    );
+
+    // clear_x()
+    // |- clear_email()
+    // |- ...
+    let clear_setter_name = format_ident!("{}{}", CLEAR_PREFIX, field_name);
+    let clear_setter = context.derive_mutator(
+        &clear_setter_name,
+        quote! {},
+        context.derive_assign(
+            quote! { ::std::option::Option::None },
+            quote! { self.#field_name.take() },
+        ),
+    );
+
+    // take_x()
+    // |- take_email()
+    // |- ...
+    let take_setter_name = format_ident!("{}{}", OPTION_TAKE_PREFIX, field_name);
+    let swap = context.derive_swap(quote! { self.#field_name.take() });
+    let clone_bound = context.derive_clone_bound(field_type, context.history.is_some());
+    let take_setter = quote! {
+        #vis fn #take_setter_name(&mut self) -> #field_type #clone_bound {
+            #swap
+            #mark
+            old
+        }
+    };
+
+    vec![setter, clear_setter, take_setter]
+}
+
+/// `#[setter(replace)]`
+///
+/// - replace_x(T) -> T, or replace_x(T) -> Option<T> for `Option<T>`
+fn derive_replace_setter(context: &SetterContext) -> proc_macro2::TokenStream {
+    let SetterContext {
+        vis,
+        field_name,
+        field_type,
+        ..
+    } = context;
+
+    // replace_x(..)
+    // |- replace_state(..)
+    // |- ...
+    let replace_setter_name = format_ident!("{}{}", REPLACE_PREFIX, field_name);
+    let mark = context.derive_mark();
+    let clone_bound = context.derive_clone_bound(field_type, context.history.is_some());
+    if try_predicate_is_option(field_type) {
+        let inner_type = try_unwrap_option(field_type);
+        let swap = context.derive_swap(quote! { self.#field_name.replace(#field_name) });
+        quote! {
+            #vis fn #replace_setter_name(&mut self, #field_name: #inner_type) -> #field_type #clone_bound {
+                #swap
+                #mark
+                old
+            }
+        }
+    } else {
+        let swap =
+            context.derive_swap(quote! { ::std::mem::replace(&mut self.#field_name, #field_name) });
+        quote! {
+            #vis fn #replace_setter_name(&mut self, #field_name: #field_type) -> #field_type #clone_bound {
+                #swap
+                #mark
+                old
+            }
+        }
+    }
+}
+
+/// `#[setter(update)]`
+///
+/// - update_x(impl FnOnce(&mut T))
+fn derive_update_setter(context: &SetterContext) -> proc_macro2::TokenStream {
+    let SetterContext {
+        field_name,
+        field_type,
+        ..
+    } = context;
+
+    // update_x(..)
+    // |- update_state(..)
+    // |- ...
+    let update_setter_name = format_ident!("{}{}", UPDATE_PREFIX, field_name);
+    context.derive_modifier(
+        &update_setter_name,
+        quote! { f: impl ::std::ops::FnOnce(&mut #field_type) },
+        quote! { f(&mut self.#field_name); },
+    )
+}
+
+/// `Cell<T>`, `RefCell<T>`, `Mutex<T>`, `RwLock<T>`, `Atomic*`, all through `&self`
+///
+/// - set_x(T)
+/// - `#[setter(replace)]`: replace_x(T) -> T
+/// - `#[setter(update)]`: update_x(impl FnOnce(&mut T)), `Cell<T>` needs `T: Copy`, atomics have none
+///
+/// The `#[setter(notify)]` hook is called through `&self` with a clone of the new value, or with
+/// clones of both values for `update_x(..)` of `RefCell`/`Mutex`/`RwLock`: no borrow or lock is held.
+fn derive_interior_setters(
+    context: &SetterContext,
+    interior: &Interior,
+    replace: bool,
+    update: bool,
+) -> Vec<proc_macro2::TokenStream> {
+    let SetterContext {
+        vis, field_name, ..
+    } = context;
+    let value_type = &interior.value_type;
+    let write = interior.derive_write(field_name);
+    let ordering = quote! { ::std::sync::atomic::Ordering::SeqCst };
+    let notify = context.derive_notify(quote! { &new });
+    let clone_bound = context.derive_clone_bound(value_type, context.notify.is_some());
+
+    let replace_body = match interior.kind {
+        InteriorKind::Cell | InteriorKind::RefCell => {
+            quote! { self.#field_name.replace(#field_name) }
+        }
+        InteriorKind::Atomic => quote! { self.#field_name.swap(#field_name, #ordering) },
+        _ => quote! { ::std::mem::replace(&mut *#write, #field_name) },
+    };
+    // let new = x.clone(); let old = replace(x); hook(field, &old, &new);
+    let notify_replace = quote! {
+        let new = ::std::clone::Clone::clone(&#field_name);
+        let old = #replace_body;
+        #notify
     };
 
-    let x = Some("");
-    x.unwrap_or_default();
+    let mut setters = Vec::new();
+
+    // set_x(..)
+    // |- set_hits(..)
+    // |- ...
+    let set_body = match interior.kind {
+        _ if context.notify.is_some() => notify_replace.clone(),
+        InteriorKind::Cell => quote! { self.#field_name.set(#field_name); },
+        InteriorKind::Atomic => quote! { self.#field_name.store(#field_name, #ordering); },
+        _ => quote! { *#write = #field_name; },
+    };
+    setters.push(context.derive_shared_mutator(
+        &context.setter_name(),
+        quote! { #field_name: #value_type },
+        clone_bound.clone(),
+        set_body,
+    ));
+
+    // replace_x(..)
+    // |- replace_hits(..)
+    // |- ...
+    if replace {
+        let replace_setter_name = format_ident!("{}{}", REPLACE_PREFIX, field_name);
+        let replace_body = if context.notify.is_some() {
+            quote! {
+                #notify_replace
+                old
+            }
+        } else {
+            replace_body
+        };
+        setters.push(quote! {
+            #vis fn #replace_setter_name(&self, #field_name: #value_type) -> #value_type #clone_bound {
+                #replace_body
+            }
+        });
+    }
+
+    // update_x(..)
+    // |- update_hits(..)
+    // |- ...
+    if update && interior.kind != InteriorKind::Atomic {
+        let update_setter_name = format_ident!("{}{}", UPDATE_PREFIX, field_name);
+        let update_body = match interior.kind {
+            InteriorKind::Cell if context.notify.is_some() => quote! {
+                let old = self.#field_name.get();
+                let mut new = old;
+                f(&mut new);
+                self.#field_name.set(new);
+                #notify
+            },
+            InteriorKind::Cell => quote! {
+                let mut value = self.#field_name.get();
+                f(&mut value);
+                self.#field_name.set(value);
+            },
+            _ if context.notify.is_some() => quote! {
+                let (old, new) = {
+                    let mut value = #write;
+                    let old = ::std::clone::Clone::clone(&*value);
+                    f(&mut *value);
+                    (old, ::std::clone::Clone::clone(&*value))
+                };
+                #notify
+            },
+            _ => quote! { f(&mut *#write); },
+        };
+        // `Cell<T>` is `Copy` already.
+        let update_bound = if interior.kind == InteriorKind::Cell {
+            quote! {}
+        } else {
+            clone_bound
+        };
+        setters.push(context.derive_shared_mutator(
+            &update_setter_name,
+            quote! { f: impl ::std::ops::FnOnce(&mut #value_type) },
+            update_bound,
+            update_body,
+        ));
+    }
+
+    setters
+}
+
+/// The hook of `#[setter(notify)]`, on the struct or on a field.
+///
+/// - `#[setter(notify)]` -> `on_change`
+/// - `#[setter(notify = on_field_change)]` -> `on_field_change`
+/// - `#[setter(notify = false)]` -> None
+fn try_parse_notify(
+    field_args: &AttributeArgs,
+    struct_args: &AttributeArgs,
+) -> syn::Result<Option<Ident>> {
+    let notify = field_args
+        .get(SETTER_ATTR_NOTIFY)
+        .or_else(|| struct_args.get(SETTER_ATTR_NOTIFY));
+
+    match notify {
+        None => Ok(None),
+        Some(AttributeArg {
+            value: Some(Expr::Path(hook)),
+            ..
+        }) => match hook.path.get_ident() {
+            Some(hook) => Ok(Some(hook.clone())),
+            None => Err(syn::Error::new_spanned(
+                hook,
+                "expected the name of a method, e.g. `notify = on_change`",
+            )),
+        },
+        Some(AttributeArg { value: None, .. }) => {
+            Ok(Some(format_ident!("{}", NOTIFY_DEFAULT_HOOK)))
+        }
+        Some(AttributeArg {
+            value:
+                Some(Expr::Lit(ExprLit {
+                    lit: Lit::Bool(flag),
+                    ..
+                })),
+            ..
+        }) => Ok(flag.value.then(|| format_ident!("{}", NOTIFY_DEFAULT_HOOK))),
+        Some(AttributeArg { key, .. }) => Err(syn::Error::new_spanned(
+            key,
+            "expected `notify`, `notify = true|false` or `notify = on_change`",
+        )),
+    }
+}
+
+/// The element name of the collection mutators, if requested.
+///
+/// - `#[setter(collection = "activity")]` -> `activity`
+/// - `#[setter(collection)]` -> `#[builder(method = "activity")]`, or the field name
+/// - `#[builder(method = "activity")]` alone -> `activity`
+/// - `#[setter(collection = false)]` -> None
+fn try_parse_element_name(field: &Field, field_args: &AttributeArgs) -> syn::Result<Option<Ident>> {
+    let field_name = field.ident.as_ref().unwrap();
+    let builder_method = try_parse_builder_method(field)?;
+
+    match field_args.get(SETTER_ATTR_COLLECTION) {
+        Some(AttributeArg {
+            value:
+                Some(Expr::Lit(ExprLit {
+                    lit: Lit::Str(element_name),
+                    ..
+                })),
+            ..
+        }) => Ok(Some(element_name.parse()?)),
+        Some(_) => match field_args.try_flag(SETTER_ATTR_COLLECTION)? {
+            Some(true) => Ok(Some(builder_method.unwrap_or_else(|| field_name.clone()))),
+            _ => Ok(None),
+        },
+        None => Ok(builder_method),
+    }
+}
+
+/// The method adding one element to a non-map collection.
+fn derive_collection_push(kind: CollectionKind) -> Ident {
+    match kind {
+        CollectionKind::Vec => format_ident!("push"),
+        CollectionKind::VecDeque => format_ident!("push_back"),
+        CollectionKind::Set | CollectionKind::Map => format_ident!("insert"),
+    }
+}
+
+/// `Vec<T>`, `VecDeque<T>`, `HashSet<T>`, `BTreeSet<T>`
+///
+/// - add_x(T)
+/// - extend_xs(impl IntoIterator<Item = T>)
+/// - remove_x_at(usize) -> T, `Vec`/`VecDeque` only
+/// - remove_x(&T) -> bool, sets only
+/// - clear_xs()
+///
+/// `HashMap<K, V>`, `BTreeMap<K, V>`
+///
+/// - insert_x(K, V)
+/// - extend_xs(impl IntoIterator<Item = (K, V)>)
+/// - remove_x(&K) -> Option<V>
+/// - clear_xs()
+fn derive_collection_setters(
+    context: &SetterContext,
+    kind: CollectionKind,
+    element_name: &Ident,
+) -> Vec<proc_macro2::TokenStream> {
+    let SetterContext {
+        vis,
+        field_name,
+        field_type,
+        ..
+    } = context;
+    let type_arguments = try_extract_type_arguments(field_type);
+    let element_type = type_arguments[0];
+    let mark = context.derive_mark();
+    let clone_bound = context.derive_clone_bound(field_type, context.is_observed());
+
+    let mut setters = Vec::new();
+
+    // add_x(..)/insert_x(..)
+    // |- add_activity(..)
+    // |- insert_header(..)
+    // |- ...
+    let (item_type, remove_setter) = match kind {
+        CollectionKind::Map => {
+            let value_type = type_arguments[1];
+            let insert_setter_name = format_ident!("{}{}", MAP_INSERT_PREFIX, element_name);
+            setters.push(context.derive_modifier(
+                &insert_setter_name,
+                quote! { key: #element_type, value: #value_type },
+                quote! { self.#field_name.insert(key, value); },
+            ));
+
+            let remove_setter_name = format_ident!("{}{}", COLLECTION_REMOVE_PREFIX, element_name);
+            let remove = context.derive_modify(quote! {
+                let removed = self.#field_name.remove(key);
+            });
+            let remove_setter = quote! {
+                #vis fn #remove_setter_name(&mut self, key: &#element_type) -> ::std::option::Option<#value_type> #clone_bound {
+                    #remove
+                    #mark
+                    removed
+                }
+            };
+
+            (quote! { (#element_type, #value_type) }, remove_setter)
+        }
+        _ => {
+            let push = derive_collection_push(kind);
+            let add_setter_name = format_ident!("{}{}", COLLECTION_ADD_PREFIX, element_name);
+            setters.push(context.derive_modifier(
+                &add_setter_name,
+                quote! { #element_name: #element_type },
+                quote! { self.#field_name.#push(#element_name); },
+            ));
+
+            let remove_setter = match kind {
+                CollectionKind::Set => {
+                    let remove_setter_name =
+                        format_ident!("{}{}", COLLECTION_REMOVE_PREFIX, element_name);
+                    let remove = context.derive_modify(quote! {
+                        let removed = self.#field_name.remove(#element_name);
+                    });
+                    quote! {
+                        #vis fn #remove_setter_name(&mut self, #element_name: &#element_type) -> bool #clone_bound {
+                            #remove
+                            #mark
+                            removed
+                        }
+                    }
+                }
+                _ => {
+                    let remove_setter_name = format_ident!(
+                        "{}{}{}",
+                        COLLECTION_REMOVE_PREFIX,
+                        element_name,
+                        COLLECTION_REMOVE_AT_SUFFIX
+                    );
+                    let removed_type = if kind == CollectionKind::Vec {
+                        quote! { #element_type }
+                    } else {
+                        quote! { ::std::option::Option<#element_type> }
+                    };
+                    let remove = context.derive_modify(quote! {
+                        let removed = self.#field_name.remove(index);
+                    });
+                    quote! {
+                        #vis fn #remove_setter_name(&mut self, index: usize) -> #removed_type #clone_bound {
+                            #remove
+                            #mark
+                            removed
+                        }
+                    }
+                }
+            };
+
+            (quote! { #element_type }, remove_setter)
+        }
+    };
+
+    // extend_xs(..)
+    // |- extend_activities(..)
+    // |- ...
+    let extend_setter_name = format_ident!("{}{}", COLLECTION_EXTEND_PREFIX, field_name);
+    setters.push(context.derive_modifier(
+        &extend_setter_name,
+        quote! { #field_name: impl ::std::iter::IntoIterator<Item = #item_type> },
+        quote! { self.#field_name.extend(#field_name); },
+    ));
+
+    // remove_x_at(..)/remove_x(..)
+    // |- remove_activity_at(..)
+    // |- remove_header(..)
+    // |- ...
+    setters.push(remove_setter);
+
+    // clear_xs()
+    // |- clear_activities()
+    // |- ...
+    let clear_setter_name = format_ident!("{}{}", CLEAR_PREFIX, field_name);
+    setters.push(context.derive_modifier(
+        &clear_setter_name,
+        quote! {},
+        quote! { self.#field_name.clear(); },
+    ));
 
-    TokenStream::from(expanded)
+    setters
 }
@@ -0,0 +1,318 @@
+/*
+ * Copyright © 2024 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// to_string
+
+// ----------------------------------------------------------------
+
+use proc_macro::TokenStream;
+
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{parse_quote, DeriveInput, Field, Ident, LitStr, Type, WherePredicate};
+use synext::{try_derive_input, try_parse_named_fields};
+
+use crate::attribute::{try_select_fields, ArgShape, AttributeArgs, AttributeKey, SELECTION_KEYS};
+use crate::sensitive::Sensitive;
+use crate::types::{try_predicate_is_any_type, try_predicate_is_collection};
+
+// ----------------------------------------------------------------
+
+const TO_STRING_ATTR_NAME: &str = "to_string";
+const TO_STRING_ATTR_RENAME: &str = "rename";
+const TO_STRING_ATTR_FORMAT: &str = "format";
+const TO_STRING_ATTR_DEBUG: &str = "debug";
+const TO_STRING_ATTR_INCLUDE_FIELD_NAMES: &str = "include_field_names";
+
+const TO_STRING_KEYS: &[&[AttributeKey]] = &[
+    SELECTION_KEYS,
+    &[
+        (TO_STRING_ATTR_RENAME, ArgShape::Str),
+        (TO_STRING_ATTR_FORMAT, ArgShape::Str),
+        (TO_STRING_ATTR_DEBUG, ArgShape::Flag),
+        (TO_STRING_ATTR_INCLUDE_FIELD_NAMES, ArgShape::Flag),
+    ],
+];
+
+// ----------------------------------------------------------------
+
+/// How a field is formatted.
+#[derive(Clone, Copy, PartialEq)]
+enum Formatting {
+    /// `{}`
+    Display,
+    /// `{:?}`
+    Debug,
+    /// `{:x}`, `{:e}`, ..., a trait the bounds don't cover.
+    Other,
+}
+
+// ----------------------------------------------------------------
+
+pub(crate) fn derive_to_string(input: TokenStream) -> TokenStream {
+    let derive_input = try_derive_input(input);
+
+    match try_derive_to_string(&derive_input) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+fn try_derive_to_string(derive_input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let struct_name = &derive_input.ident;
+    let struct_args =
+        AttributeArgs::try_parse(TO_STRING_ATTR_NAME, TO_STRING_KEYS, &derive_input.attrs)?;
+
+    let fields = try_parse_named_fields(derive_input);
+    let (template, arguments, bounds) = match struct_args.try_str(TO_STRING_ATTR_FORMAT)? {
+        Some(format) => try_derive_format_template(&format, fields)?,
+        None => try_derive_field_list(derive_input, &struct_args)?,
+    };
+
+    let mut generics = derive_input.generics.clone();
+    if generics.type_params().next().is_some() {
+        generics.make_where_clause().predicates.extend(bounds);
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let expanded = quote! {
+        impl #impl_generics ::std::fmt::Display for #struct_name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                ::std::write!(f, #template, #(#arguments),*)
+            }
+        }
+    };
+
+    Ok(expanded)
+}
+
+// ----------------------------------------------------------------
+
+/// `User(id=10086, name=photowey, hobby=["rust"])`, or `User(10086, photowey, ["rust"])`
+/// with `#[to_string(include_field_names = false)]`.
+fn try_derive_field_list(
+    derive_input: &DeriveInput,
+    struct_args: &AttributeArgs,
+) -> syn::Result<(String, Vec<proc_macro2::TokenStream>, Vec<WherePredicate>)> {
+    let include_field_names = struct_args
+        .try_flag(TO_STRING_ATTR_INCLUDE_FIELD_NAMES)?
+        .unwrap_or(true);
+
+    let mut entries = Vec::new();
+    let mut arguments = Vec::new();
+    let mut bounds = Vec::new();
+
+    let fields = try_parse_named_fields(derive_input);
+    for (field, field_args) in try_select_fields(TO_STRING_ATTR_NAME, TO_STRING_KEYS, fields)? {
+        let field_name = field.ident.as_ref().unwrap();
+
+        // #[sensitive]: password=***
+        let sensitive = Sensitive::try_parse(field)?;
+        let formatting = match sensitive {
+            Some(_) => Formatting::Display,
+            None => try_predicate_formatting(field, &field_args, struct_args)?,
+        };
+        let placeholder = match formatting {
+            Formatting::Debug => "{:?}",
+            _ => "{}",
+        };
+
+        if include_field_names {
+            let label = match field_args.try_str(TO_STRING_ATTR_RENAME)? {
+                Some(rename) => rename.value(),
+                None => field_name.unraw().to_string(),
+            };
+            entries.push(format!("{}={}", escape(&label), placeholder));
+        } else {
+            entries.push(placeholder.to_string());
+        }
+
+        match sensitive {
+            Some(sensitive) => {
+                arguments.push(sensitive.derive_masked(field_name));
+                bounds.extend(sensitive.derive_bound(field));
+            }
+            None => {
+                arguments.push(quote! { self.#field_name });
+                bounds.extend(derive_bound(&field.ty, formatting));
+            }
+        }
+    }
+
+    let template = format!(
+        "{}({})",
+        escape(&derive_input.ident.unraw().to_string()),
+        entries.join(", ")
+    );
+
+    Ok((template, arguments, bounds))
+}
+
+/// `#[to_string(format = "{name} <{email}>")]`
+///
+/// Every `{field}` must name a field, `{field:?}`, `{field:>8}`, ... keep their spec.
+/// `#[sensitive]` fields are masked here as well.
+fn try_derive_format_template<'a>(
+    format: &LitStr,
+    fields: impl IntoIterator<Item = &'a Field>,
+) -> syn::Result<(String, Vec<proc_macro2::TokenStream>, Vec<WherePredicate>)> {
+    let fields: Vec<&Field> = fields.into_iter().collect();
+
+    let mut arguments = Vec::new();
+    let mut bounds = Vec::new();
+    let mut referenced: Vec<String> = Vec::new();
+
+    for (name, formatting) in try_parse_placeholders(format)? {
+        let Some(field) = fields
+            .iter()
+            .find(|field| field.ident.as_ref().unwrap().unraw() == name)
+        else {
+            return Err(syn::Error::new_spanned(
+                format,
+                format!("unknown field `{}` in `format`", name),
+            ));
+        };
+
+        let field_name = field.ident.as_ref().unwrap();
+        let sensitive = Sensitive::try_parse(field)?;
+        let value = match &sensitive {
+            Some(sensitive) => {
+                bounds.extend(sensitive.derive_bound(field));
+                sensitive.derive_masked(field_name)
+            }
+            None => {
+                bounds.extend(derive_bound(&field.ty, formatting));
+                quote! { self.#field_name }
+            }
+        };
+
+        if !referenced.contains(&name) {
+            let argument: Ident = syn::parse_str(&name)?;
+            arguments.push(quote! { #argument = #value });
+            referenced.push(name);
+        }
+    }
+
+    Ok((format.value(), arguments, bounds))
+}
+
+/// Try to parse the `{name}`/`{name:spec}` placeholders of a `format` template.
+///
+/// `{{` and `}}` are escaped braces, positional `{}`/`{0}` placeholders are rejected.
+fn try_parse_placeholders(format: &LitStr) -> syn::Result<Vec<(String, Formatting)>> {
+    let template = format.value();
+    let mut placeholders = Vec::new();
+
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+            }
+            '{' => {
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => placeholder.push(c),
+                        None => {
+                            return Err(syn::Error::new_spanned(format, "unclosed `{` in `format`"))
+                        }
+                    }
+                }
+
+                let (name, spec) = placeholder
+                    .split_once(':')
+                    .unwrap_or((placeholder.as_str(), ""));
+                let name = name.trim();
+                if syn::parse_str::<Ident>(name).is_err() {
+                    return Err(syn::Error::new_spanned(
+                        format,
+                        format!("expected a field name in `{{{}}}` of `format`", placeholder),
+                    ));
+                }
+
+                let formatting = if spec.ends_with('?') {
+                    Formatting::Debug
+                } else if spec.ends_with(|c: char| c.is_ascii_alphabetic()) {
+                    Formatting::Other
+                } else {
+                    Formatting::Display
+                };
+                placeholders.push((name.to_string(), formatting));
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+            }
+            '}' => {
+                return Err(syn::Error::new_spanned(
+                    format,
+                    "unmatched `}` in `format`, use `}}`",
+                ))
+            }
+            _ => {}
+        }
+    }
+
+    Ok(placeholders)
+}
+
+// ----------------------------------------------------------------
+
+/// `Option`s, collections, arrays, slices and tuples print with `Debug` unless `#[to_string(debug = false)]`,
+/// everything else prints with `Display` unless `#[to_string(debug)]`.
+fn try_predicate_formatting(
+    field: &Field,
+    field_args: &AttributeArgs,
+    struct_args: &AttributeArgs,
+) -> syn::Result<Formatting> {
+    let debug = field_args.try_flag_or_else(
+        struct_args,
+        TO_STRING_ATTR_DEBUG,
+        try_predicate_is_debug_only(&field.ty),
+    )?;
+
+    Ok(if debug {
+        Formatting::Debug
+    } else {
+        Formatting::Display
+    })
+}
+
+/// Try to predicate that [`syn::Type`] implements `Debug` but not `Display`.
+fn try_predicate_is_debug_only(ty: &Type) -> bool {
+    match ty {
+        Type::Array(_) | Type::Slice(_) | Type::Tuple(_) => true,
+        Type::Reference(reference) => try_predicate_is_debug_only(&reference.elem),
+        Type::Group(group) => try_predicate_is_debug_only(&group.elem),
+        Type::Paren(paren) => try_predicate_is_debug_only(&paren.elem),
+        ty => try_predicate_is_any_type(&["Option"], 1, ty) || try_predicate_is_collection(ty),
+    }
+}
+
+/// `T: Display` / `T: Debug` for the formatted field types of generic structs.
+fn derive_bound(field_type: &Type, formatting: Formatting) -> Option<WherePredicate> {
+    match formatting {
+        Formatting::Display => Some(parse_quote!(#field_type: ::std::fmt::Display)),
+        Formatting::Debug => Some(parse_quote!(#field_type: ::std::fmt::Debug)),
+        Formatting::Other => None,
+    }
+}
+
+/// Escape the braces of a literal part of a format string.
+fn escape(literal: &str) -> String {
+    literal.replace('{', "{{").replace('}', "}}")
+}
@@ -0,0 +1,186 @@
+/*
+ * Copyright © 2024 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// track
+
+// ----------------------------------------------------------------
+
+use proc_macro::TokenStream;
+
+use quote::{format_ident, quote};
+use syn::parse::Parser;
+use syn::{parse_quote, Data, DataStruct, DeriveInput, Field, Fields, Generics, Ident};
+
+use crate::attribute::{derive_hidden_field_impls, try_predicate_is_hidden_field};
+
+// ----------------------------------------------------------------
+
+/// The hidden bit set of the changed fields.
+pub(crate) const TRACK_CHANGES_FIELD: &str = "__lombok_changes";
+/// `#[setter(track_changes)]`, added by `#[track_changes]` for `Setter`.
+pub(crate) const ATTR_TRACK_CHANGES: &str = "track_changes";
+
+const BITS: usize = u64::BITS as usize;
+
+// ----------------------------------------------------------------
+
+pub(crate) fn derive_track_changes(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = proc_macro2::TokenStream::from(args);
+    if !args.is_empty() {
+        let err = syn::Error::new_spanned(args, "expected `#[track_changes]`");
+        return TokenStream::from(err.to_compile_error());
+    }
+
+    let derive_input = syn::parse_macro_input!(input as DeriveInput);
+
+    match try_derive_track_changes(derive_input) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+fn try_derive_track_changes(
+    mut derive_input: DeriveInput,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let Data::Struct(DataStruct {
+        fields: Fields::Named(named_fields),
+        ..
+    }) = &mut derive_input.data
+    else {
+        return Err(syn::Error::new_spanned(
+            &derive_input.ident,
+            "`#[track_changes]` expects a struct with named fields",
+        ));
+    };
+
+    let field_names: Vec<String> = named_fields
+        .named
+        .iter()
+        .filter(|field| !try_predicate_is_hidden_field(field))
+        .map(|field| field.ident.as_ref().unwrap().to_string())
+        .collect();
+    let fields = field_names.len();
+    let words = fields.div_ceil(BITS).max(1);
+
+    // __lombok_changes: __LombokXChanges
+    let changes = format_ident!("{}", TRACK_CHANGES_FIELD);
+    let changes_name = format_ident!("__Lombok{}Changes", derive_input.ident);
+    let changes_field = Field::parse_named.parse2(quote! {
+        #[doc(hidden)]
+        #changes: #changes_name
+    })?;
+    named_fields.named.push(changes_field);
+
+    let setter_attr = format_ident!("{}", ATTR_TRACK_CHANGES);
+    derive_input
+        .attrs
+        .push(parse_quote!(#[setter(#setter_attr)]));
+
+    let struct_name = &derive_input.ident;
+    let vis = &derive_input.vis;
+    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+
+    let changes_impls = derive_hidden_field_impls(&changes_name, &Generics::default());
+
+    let expanded = quote! {
+        #derive_input
+
+        /// The `[u64; N]` bit set of the changed fields, see `#[track_changes]`.
+        #[doc(hidden)]
+        #[derive(Clone, Copy, Default, Debug)]
+        struct #changes_name {
+            bits: [u64; #words],
+            /// The number of `reset_changes()` calls, the bits recorded by `#[history]` before one are stale.
+            resets: u64,
+        }
+
+        #changes_impls
+
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            /// The fields changed through a setter since construction or the last `reset_changes()`.
+            ///
+            /// `undo()` and `redo()` of `#[history]` restore the state of the field before the change,
+            /// or mark it if `reset_changes()` was called since.
+            #vis fn changed_fields(&self) -> impl ::std::iter::Iterator<Item = &'static str> + '_ {
+                const FIELDS: [&str; #fields] = [#(#field_names),*];
+
+                FIELDS
+                    .iter()
+                    .enumerate()
+                    .filter(move |(index, _)| self.#changes.bits[index / #BITS] & (1 << (index % #BITS)) != 0)
+                    .map(|(_, field)| *field)
+            }
+
+            /// Whether any field changed through a setter since construction or the last `reset_changes()`.
+            #vis fn is_dirty(&self) -> bool {
+                self.#changes.bits.iter().any(|word| *word != 0)
+            }
+
+            /// Forget the changed fields.
+            #vis fn reset_changes(&mut self) {
+                self.#changes = #changes_name {
+                    bits: ::std::default::Default::default(),
+                    resets: self.#changes.resets.wrapping_add(1),
+                };
+            }
+        }
+    };
+
+    Ok(expanded)
+}
+
+/// Mark the field at `index` as changed, see [`derive_track_changes`].
+pub(crate) fn derive_mark_changed(index: usize) -> proc_macro2::TokenStream {
+    let changes: Ident = format_ident!("{}", TRACK_CHANGES_FIELD);
+    let word = index / BITS;
+    let bit = index % BITS;
+
+    quote! {
+        self.#changes.bits[#word] |= 1 << #bit;
+    }
+}
+
+/// The `u64` state of the field at `index` kept by `#[history]`: the `resets` shifted left,
+/// and the bit of the field.
+pub(crate) fn derive_changed_state(index: usize) -> proc_macro2::TokenStream {
+    let changes: Ident = format_ident!("{}", TRACK_CHANGES_FIELD);
+    let word = index / BITS;
+    let bit = index % BITS;
+
+    quote! {
+        (self.#changes.resets << 1 | (self.#changes.bits[#word] >> #bit & 1))
+    }
+}
+
+/// Restore the `state` of the field at `index` on `undo()`/`redo()`, see [`derive_changed_state`].
+///
+/// A state from before the last `reset_changes()` can't be trusted, the field is marked instead.
+pub(crate) fn derive_restore_changed(
+    index: usize,
+    state: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let changes: Ident = format_ident!("{}", TRACK_CHANGES_FIELD);
+    let word = index / BITS;
+    let bit = index % BITS;
+
+    quote! {
+        if #state >> 1 == self.#changes.resets && #state & 1 == 0 {
+            self.#changes.bits[#word] &= !(1 << #bit);
+        } else {
+            self.#changes.bits[#word] |= 1 << #bit;
+        }
+    }
+}
@@ -0,0 +1,126 @@
+/*
+ * Copyright © 2024 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// types
+
+// ----------------------------------------------------------------
+
+use syn::{GenericArgument, Ident, PathArguments, Type};
+
+// ----------------------------------------------------------------
+
+/// Try to extract the ident of the last path segment of [`syn::Type`].
+///
+/// - `String` -> `String`
+/// - `std::sync::Arc<T>` -> `Arc`
+/// - `&str` -> None
+pub(crate) fn try_extract_type_ident(ty: &Type) -> Option<&Ident> {
+    match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => {
+            type_path.path.segments.last().map(|segment| &segment.ident)
+        }
+        _ => None,
+    }
+}
+
+/// Try to extract the generic type arguments of the last path segment of [`syn::Type`].
+///
+/// - `Vec<T>` -> [T]
+/// - `HashMap<K, V>` -> [K, V]
+/// - `String` -> []
+pub(crate) fn try_extract_type_arguments(ty: &Type) -> Vec<&Type> {
+    match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => {
+            match type_path
+                .path
+                .segments
+                .last()
+                .map(|segment| &segment.arguments)
+            {
+                Some(PathArguments::AngleBracketed(bracketed)) => bracketed
+                    .args
+                    .iter()
+                    .filter_map(|argument| match argument {
+                        GenericArgument::Type(ty) => Some(ty),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Try to predicate that the last path segment of [`syn::Type`] is one of `idents`
+/// and carries `target_types` generic type arguments.
+pub(crate) fn try_predicate_is_any_type(idents: &[&str], target_types: usize, ty: &Type) -> bool {
+    match try_extract_type_ident(ty) {
+        Some(ident) => {
+            idents.iter().any(|target| ident == target)
+                && try_extract_type_arguments(ty).len() == target_types
+        }
+        None => false,
+    }
+}
+
+// ----------------------------------------------------------------
+
+/// The collection types recognized by `Builder` and `Setter`.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum CollectionKind {
+    /// `Vec<T>`
+    Vec,
+    /// `VecDeque<T>`
+    VecDeque,
+    /// `HashSet<T>`/`BTreeSet<T>`
+    Set,
+    /// `HashMap<K, V>`/`BTreeMap<K, V>`
+    Map,
+}
+
+/// Try to predicate the [`CollectionKind`] of [`syn::Type`].
+///
+/// - `Vec<String>` -> `Some(CollectionKind::Vec)`
+/// - `HashMap<String, String>` -> `Some(CollectionKind::Map)`
+/// - `String` -> None
+pub(crate) fn try_predicate_collection_kind(ty: &Type) -> Option<CollectionKind> {
+    if try_predicate_is_any_type(&["Vec"], 1, ty) {
+        return Some(CollectionKind::Vec);
+    }
+    if try_predicate_is_any_type(&["VecDeque"], 1, ty) {
+        return Some(CollectionKind::VecDeque);
+    }
+    if try_predicate_is_any_type(&["BTreeSet"], 1, ty)
+        || try_predicate_is_any_type(&["HashSet"], 1, ty)
+        || try_predicate_is_any_type(&["HashSet"], 2, ty)
+    {
+        return Some(CollectionKind::Set);
+    }
+    if try_predicate_is_any_type(&["BTreeMap"], 2, ty)
+        || try_predicate_is_any_type(&["HashMap"], 2, ty)
+        || try_predicate_is_any_type(&["HashMap"], 3, ty)
+    {
+        return Some(CollectionKind::Map);
+    }
+
+    None
+}
+
+/// Try to predicate that [`syn::Type`] is one of the [`CollectionKind`] types.
+pub(crate) fn try_predicate_is_collection(ty: &Type) -> bool {
+    try_predicate_collection_kind(ty).is_some()
+}
@@ -0,0 +1,154 @@
+/*
+ * Copyright © 2024 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// value
+
+// ----------------------------------------------------------------
+
+use proc_macro::TokenStream;
+
+use quote::{format_ident, quote, ToTokens};
+use syn::punctuated::Punctuated;
+use syn::{parse_quote, DeriveInput, Path, Token};
+use synext::{try_derive_input, try_parse_named_fields};
+
+use crate::attribute::AttributeArgs;
+use crate::constructor::derive_all_args_constructor;
+use crate::data::{DATA_ATTR_NAME, DATA_ATTR_SETTER, DATA_KEYS};
+use crate::eq::derive_equals_and_hash_code;
+use crate::getter::{derive_getter, GETTER_ATTR_MUT, GETTER_ATTR_NAME, GETTER_KEYS};
+use crate::to_string::derive_to_string;
+use crate::with::{derive_with, WITH_ATTR_NAME};
+
+// ----------------------------------------------------------------
+
+/// The hidden associated const both `Value` and `Setter` define, deriving both is a duplicate definition.
+///
+/// The name is what rustc reports, so it says what went wrong.
+const IMMUTABLE_MARKER: &str = "Value_cannot_be_combined_with_Setter_or_Data";
+
+/// The derives that add setters.
+const MUTABLE_DERIVES: &[&str] = &["Setter", "Data"];
+
+// ----------------------------------------------------------------
+
+pub(crate) fn derive_value(input: TokenStream) -> TokenStream {
+    let derive_input = try_derive_input(input);
+
+    match try_derive_value(derive_input) {
+        Ok(expanded) => expanded,
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+fn try_derive_value(mut derive_input: DeriveInput) -> syn::Result<TokenStream> {
+    try_check_mutable_derives(&derive_input)?;
+    try_check_mut_getters(&derive_input)?;
+
+    // The getters and withers inherit the visibility of the struct, like the constructor.
+    // Prepended, so an explicit `#[getter(vis = "...")]`/`#[with(vis = "...")]` still wins.
+    let vis = derive_input.vis.to_token_stream().to_string();
+    for attr_name in [GETTER_ATTR_NAME, WITH_ATTR_NAME] {
+        let attr_name = format_ident!("{}", attr_name);
+        derive_input
+            .attrs
+            .insert(0, parse_quote!(#[#attr_name(vis = #vis)]));
+    }
+
+    let input = TokenStream::from(derive_input.to_token_stream());
+
+    Ok(TokenStream::from_iter(vec![
+        derive_getter(input.clone()),
+        derive_all_args_constructor(input.clone()),
+        derive_with(input.clone()),
+        derive_equals_and_hash_code(input.clone()),
+        derive_to_string(input),
+        TokenStream::from(derive_immutable_marker(&derive_input)),
+    ]))
+}
+
+/// The hidden item that turns `#[derive(Value, Setter)]` into a duplicate definition error.
+///
+/// A derive can't see the other derives of the same `#[derive(..)]` attribute.
+pub(crate) fn derive_immutable_marker(derive_input: &DeriveInput) -> proc_macro2::TokenStream {
+    let struct_name = &derive_input.ident;
+    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+    let marker = format_ident!("{}", IMMUTABLE_MARKER);
+
+    quote! {
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            #[doc(hidden)]
+            #[allow(dead_code, non_upper_case_globals)]
+            const #marker: () = ();
+        }
+    }
+}
+
+// ----------------------------------------------------------------
+
+/// `#[derive(Value)] #[derive(Setter)]`: the later `#[derive(..)]` attributes are visible.
+fn try_check_mutable_derives(derive_input: &DeriveInput) -> syn::Result<()> {
+    let data_args = AttributeArgs::try_parse(DATA_ATTR_NAME, DATA_KEYS, &derive_input.attrs)?;
+
+    for attr in derive_input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("derive"))
+    {
+        let derives = attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+        for derive in derives.iter() {
+            let Some(segment) = derive.segments.last() else {
+                continue;
+            };
+            // #[data(setter = false)]
+            let mutable = match segment.ident.to_string().as_str() {
+                "Data" => data_args.try_flag(DATA_ATTR_SETTER)?.unwrap_or(true),
+                ident => MUTABLE_DERIVES.contains(&ident),
+            };
+            if mutable {
+                return Err(syn::Error::new_spanned(
+                    derive,
+                    format!("`Value` types are immutable, remove `{}`", segment.ident),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// `#[getter(mut)]` would hand out `&mut` to the fields of a `Value`.
+fn try_check_mut_getters(derive_input: &DeriveInput) -> syn::Result<()> {
+    let struct_args = AttributeArgs::try_parse(GETTER_ATTR_NAME, GETTER_KEYS, &derive_input.attrs)?;
+    if struct_args.try_flag(GETTER_ATTR_MUT)? == Some(true) {
+        return Err(syn::Error::new_spanned(
+            &derive_input.ident,
+            "`Value` types are immutable, remove `#[getter(mut)]`",
+        ));
+    }
+
+    for field in try_parse_named_fields(derive_input).iter() {
+        let field_args = AttributeArgs::try_parse(GETTER_ATTR_NAME, GETTER_KEYS, &field.attrs)?;
+        if field_args.try_flag(GETTER_ATTR_MUT)? == Some(true) {
+            return Err(syn::Error::new_spanned(
+                field,
+                "`Value` types are immutable, remove `#[getter(mut)]`",
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+/*
+ * Copyright © 2024 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// with
+
+// ----------------------------------------------------------------
+
+use proc_macro::TokenStream;
+
+use quote::{format_ident, quote};
+use syn::DeriveInput;
+use synext::{try_derive_input, try_parse_named_fields};
+
+use crate::attribute::{try_select_fields, AttributeArgs, AttributeKey, SELECTION_KEYS, VIS_KEYS};
+
+// ----------------------------------------------------------------
+
+const WITH_PREFIX: &str = "with_";
+const WITH_CLONED_SUFFIX: &str = "_cloned";
+
+pub(crate) const WITH_ATTR_NAME: &str = "with";
+
+const WITH_KEYS: &[&[AttributeKey]] = &[SELECTION_KEYS, VIS_KEYS];
+
+// ----------------------------------------------------------------
+
+pub(crate) fn derive_with(input: TokenStream) -> TokenStream {
+    let derive_input = try_derive_input(input);
+
+    match try_derive_withers(&derive_input) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+pub(crate) fn try_derive_withers(
+    derive_input: &DeriveInput,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let struct_name = &derive_input.ident;
+
+    let (impl_generics, ty_generics, where_clause) = &derive_input.generics.split_for_impl();
+
+    let struct_args = AttributeArgs::try_parse(WITH_ATTR_NAME, WITH_KEYS, &derive_input.attrs)?;
+
+    let mut withers = Vec::new();
+
+    let fields = try_parse_named_fields(derive_input);
+    for (field, field_args) in try_select_fields(WITH_ATTR_NAME, WITH_KEYS, fields)? {
+        let field_name = field.ident.as_ref().unwrap();
+        let field_type = &field.ty;
+
+        let vis = field_args.try_vis_or(&struct_args, &field.vis)?;
+
+        // with_x(..)
+        // |- with_id(..)
+        // |- with_name(..)
+        // |- ...
+        let wither_name = format_ident!("{}{}", WITH_PREFIX, field_name);
+        let wither = quote! {
+            #vis fn #wither_name(self, #field_name: #field_type) -> Self {
+                Self {
+                    #field_name,
+                    ..self
+                }
+            }
+        };
+        withers.push(wither);
+
+        // with_x_cloned(..)
+        // |- with_id_cloned(..)
+        // |- ...
+        //
+        // The higher-ranked bound defers `Self: Clone` to the call site.
+        let cloned_wither_name =
+            format_ident!("{}{}{}", WITH_PREFIX, field_name, WITH_CLONED_SUFFIX);
+        let cloned_wither = quote! {
+            #vis fn #cloned_wither_name(&self, #field_name: #field_type) -> Self
+            where
+                for<'__lombok> Self: ::std::clone::Clone,
+            {
+                Self {
+                    #field_name,
+                    ..::std::clone::Clone::clone(self)
+                }
+            }
+        };
+        withers.push(cloned_wither);
+    }
+
+    let expanded = quote! {
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            #(
+                #withers
+            )*
+        }
+    };
+
+    Ok(expanded)
+}
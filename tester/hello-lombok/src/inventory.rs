@@ -0,0 +1,59 @@
+/*
+ * Copyright © 2024 the original author or authors.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// inventory
+
+// ----------------------------------------------------------------
+
+use lombokrs::{Getter, Setter};
+
+// ----------------------------------------------------------------
+
+/// Accessors follow the visibility of their fields unless overridden.
+#[derive(Setter, Getter, Debug)]
+pub struct Item {
+    // pub(crate) get_sku()/sku()/set_sku()
+    pub(crate) sku: String,
+    // pub get_count()/count(), private set_count()
+    #[getter(vis = "pub")]
+    count: u32,
+    // private get_price()/price()/set_price()
+    price: u32,
+}
+
+// ----------------------------------------------------------------
+
+impl Item {
+    pub fn new(sku: String, count: u32, price: u32) -> Self {
+        Self { sku, count, price }
+    }
+
+    pub fn restock(&mut self, count: u32) {
+        self.set_count(self.count() + count);
+    }
+
+    pub fn reprice(&mut self, price: u32) {
+        self.set_price(price);
+    }
+
+    pub fn label(&self) -> String {
+        format!("{} x {}", self.sku(), self.count())
+    }
+
+    pub fn total(&self) -> u32 {
+        self.get_count() * self.get_price()
+    }
+}
@@ -18,14 +18,32 @@
 
 // ----------------------------------------------------------------
 
-use lombokrs::{Builder, Data, Getter, Setter};
+use std::any::Any;
+use std::cell::{Cell, OnceCell, RefCell};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, AtomicU64};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+
+use lombokrs::{
+    change_listeners, history, track_changes, AllArgsConstructor, Builder, Data, EqualsAndHashCode,
+    Getter, NoArgsConstructor, RedactedDebug, RequiredArgsConstructor, Setter, ToString, Value,
+    With,
+};
 
 #[cfg(test)]
 mod tests;
 
+pub mod inventory;
+
 // ----------------------------------------------------------------
 
-#[derive(Setter, Getter, Builder, Debug)]
+#[derive(AllArgsConstructor, Setter, Getter, Builder, Debug)]
+#[getter(vis = "pub")]
+#[setter(vis = "pub")]
 pub struct User {
     id: u32,
     age: u8,
@@ -36,7 +54,10 @@ pub struct User {
     activities: Vec<String>,
 }
 
-#[derive(Setter, Getter, Builder, Debug)]
+#[allow(clippy::redundant_allocation)]
+#[derive(AllArgsConstructor, Setter, Getter, Builder, Debug)]
+#[getter(vis = "pub")]
+#[setter(vis = "pub")]
 pub struct LifetimeUser<'a> {
     id: u32,
     age: u8,
@@ -45,47 +66,564 @@ pub struct LifetimeUser<'a> {
     hobby: Box<&'a str>,
 }
 
-#[derive(Data, Debug)]
-pub struct DataUser {
+#[derive(Getter, Builder, Debug)]
+#[getter(vis = "pub")]
+pub struct Buf<T, const N: usize> {
+    data: [T; N],
+    len: usize,
+}
+
+#[derive(Getter, Builder, Debug)]
+#[getter(vis = "pub")]
+pub struct Request<B = ()> {
+    url: String,
+    headers: Vec<String>,
+    #[builder(type_changing)]
+    body: B,
+}
+
+#[derive(Getter, Builder, Debug)]
+#[builder(type_changing = "B")]
+#[getter(vis = "pub")]
+pub struct Upload<'a, T: Clone, B> {
+    url: &'a str,
+    meta: T,
+    body: B,
+}
+
+#[derive(Getter, Debug)]
+#[getter(deref, vis = "pub")]
+pub struct Profile {
+    nickname: String,
+    tags: Vec<String>,
+    bio: Option<String>,
+    avatar: Option<Vec<u8>>,
+    score: Option<u32>,
+    home: PathBuf,
+    shared: Rc<String>,
+    #[getter(deref = false)]
+    email: String,
+}
+
+#[derive(Getter, Debug)]
+#[getter(mut, vis = "pub")]
+pub struct Playlist {
+    name: String,
+    #[getter(deref)]
+    tracks: Vec<String>,
+    #[getter(deref)]
+    cover: Option<Vec<u8>>,
+    plays: u64,
+}
+
+#[derive(Setter, Getter, Debug)]
+#[getter(style = "fluent", vis = "pub")]
+#[setter(vis = "pub")]
+pub struct Account {
     id: u32,
-    age: u8,
+    #[getter(style = "prefixed")]
+    name: String,
+    #[setter(skip)]
+    created_at: u64,
+    #[getter(skip)]
+    #[setter(skip)]
+    password: String,
+}
+
+#[derive(Setter, Getter, Debug)]
+#[getter(vis = "pub")]
+#[setter(vis = "pub")]
+pub struct Session {
+    #[getter(only)]
+    token: String,
+    #[setter(only)]
+    expires_at: u64,
+    secret: String,
+}
+
+#[derive(Getter, Debug)]
+#[getter(vis = "pub")]
+pub struct Template {
+    source: String,
+    #[getter(lazy = Template::parse_placeholders)]
+    placeholders: OnceCell<Vec<String>>,
+    #[getter(skip)]
+    parses: Cell<u32>,
+}
+
+#[derive(Getter, Debug)]
+#[getter(vis = "pub")]
+pub struct Document {
+    text: String,
+    #[getter(lazy = Document::count_words)]
+    words: OnceLock<usize>,
+}
+
+#[derive(Setter, Getter, Debug)]
+#[getter(vis = "pub")]
+#[setter(vis = "pub")]
+pub struct Feature {
+    name: String,
+    enabled: bool,
+    is_beta: bool,
+    #[getter(predicate)]
+    released: Option<u16>,
+    #[getter(predicate = false)]
+    dirty: bool,
+}
+
+#[derive(Setter, Getter, Debug)]
+#[getter(vis = "pub")]
+#[setter(vis = "pub")]
+pub struct Contact {
+    name: String,
+    email: Option<String>,
+    #[getter(deref)]
+    nickname: Option<String>,
+    #[getter(copy)]
+    age: Option<u8>,
+}
+
+#[derive(Setter, Getter, Debug)]
+#[getter(style = "prefixed", vis = "pub")]
+#[setter(chain, vis = "pub")]
+pub struct Settings {
+    theme: String,
+    font_size: u8,
+    proxy: Option<String>,
+    #[setter(fluent)]
+    locale: String,
+    #[setter(chain = false)]
+    debug: bool,
+}
+
+#[derive(Setter, Getter, Builder, Debug)]
+#[getter(vis = "pub")]
+#[setter(chain, vis = "pub")]
+pub struct Team {
+    name: String,
+    #[builder(method = "member")]
+    members: Vec<String>,
+    #[setter(collection = "tag")]
+    tags: BTreeSet<String>,
+    #[setter(collection = "role")]
+    roles: HashMap<String, u8>,
+    #[setter(collection = "task")]
+    backlog: VecDeque<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConnectionState {
+    Idle,
+    Connecting,
+    Connected,
+}
+
+#[derive(Setter, Getter, Debug)]
+#[getter(vis = "pub")]
+#[setter(replace, update, vis = "pub")]
+pub struct Connection {
+    #[getter(copy)]
+    state: ConnectionState,
+    retries: u32,
+    peer: Option<String>,
+}
+
+#[derive(Setter, Getter, Debug)]
+#[getter(interior, vis = "pub")]
+#[setter(chain, replace, update, interior, vis = "pub")]
+pub struct Service {
+    hits: Cell<u32>,
+    banner: RefCell<String>,
+    peers: Arc<Mutex<Vec<String>>>,
+    #[getter(poison = "recover")]
+    #[setter(poison = "recover")]
+    routes: RwLock<Vec<String>>,
+    running: AtomicBool,
+    requests: AtomicU64,
+}
+
+#[derive(Setter, Getter, Debug, Default)]
+#[getter(vis = "pub")]
+#[setter(vis = "pub")]
+pub struct Counter {
+    hits: Cell<u32>,
+}
+
+#[track_changes]
+#[derive(Setter, Getter, Builder, Clone, PartialEq, Eq, Hash, Debug)]
+#[getter(vis = "pub")]
+#[setter(vis = "pub")]
+pub struct Row {
+    id: u32,
+    name: String,
+    email: Option<String>,
+    #[setter(collection = "tag")]
+    tags: Vec<String>,
+}
+
+#[derive(Setter, Getter, Debug, Default)]
+#[getter(vis = "pub")]
+#[setter(notify, vis = "pub")]
+pub struct ViewModel {
+    #[setter(update)]
+    title: String,
+    subtitle: Option<String>,
+    #[setter(notify = on_count_change)]
+    count: u32,
+    #[setter(collection = "tag")]
+    tags: Vec<String>,
+    #[getter(interior)]
+    #[setter(interior)]
+    selected: Cell<bool>,
+    #[getter(skip)]
+    #[setter(skip)]
+    changes: RefCell<Vec<String>>,
+}
+
+#[change_listeners]
+#[derive(Setter, Getter, Clone, Debug, Default)]
+#[getter(vis = "pub")]
+#[setter(vis = "pub")]
+pub struct Form {
+    title: String,
+    #[setter(collection = "field")]
+    fields: Vec<String>,
+    #[setter(notify = false)]
+    draft: bool,
+}
+
+#[history(capacity = 3)]
+#[derive(Setter, Getter, Builder, Clone, PartialEq, Eq, Hash, Debug)]
+#[getter(vis = "pub")]
+#[setter(chain, vis = "pub")]
+pub struct Canvas {
+    color: String,
+    #[setter(replace)]
+    width: u32,
+    title: Option<String>,
+    #[setter(collection = "layer")]
+    layers: Vec<String>,
+}
+
+#[history]
+#[derive(NoArgsConstructor, Setter, Getter, Clone, PartialEq, Debug)]
+#[getter(vis = "pub")]
+#[setter(vis = "pub")]
+pub struct Layer<'a, T> {
+    name: Option<&'a str>,
+    #[setter(skip)]
+    #[getter(skip)]
+    kind: PhantomData<T>,
+}
+
+#[track_changes]
+#[history]
+#[change_listeners]
+#[derive(Setter, Getter, Default, Debug)]
+#[getter(vis = "pub")]
+#[setter(vis = "pub")]
+pub struct Sketch {
+    title: String,
+    strokes: u32,
+}
+
+#[derive(AllArgsConstructor, Getter, Debug)]
+#[all_args(name = "create", vis = "pub")]
+#[getter(vis = "pub")]
+pub struct Endpoint {
+    #[new(into)]
+    host: String,
+    port: u16,
+    #[new(into)]
+    path: Option<String>,
+}
+
+#[derive(RequiredArgsConstructor, Getter, Builder, Debug)]
+#[getter(vis = "pub")]
+pub struct Server {
+    #[new(into)]
+    host: String,
+    #[builder(default = 8080)]
+    port: u16,
+    #[builder(default)]
+    workers: usize,
+    tags: Vec<String>,
+    proxy: Option<String>,
+}
+
+#[derive(NoArgsConstructor, Getter, Builder, Debug)]
+#[getter(vis = "pub")]
+pub struct Config {
+    #[default = "anonymous"]
+    name: String,
+    #[default = 8080]
+    port: u16,
+    #[default(vec!["en".to_string()])]
+    locales: Vec<String>,
+    #[default(Some(30))]
+    timeout: Option<u64>,
+    #[default]
+    retries: u8,
+    verbose: bool,
+}
+
+#[derive(AllArgsConstructor, Getter, ToString, Debug)]
+#[getter(vis = "pub")]
+pub struct Customer {
+    id: u32,
+    #[to_string(rename = "username")]
+    name: String,
+    email: Option<String>,
+    tags: Vec<String>,
+    #[to_string(skip)]
+    password: String,
+}
+
+#[derive(AllArgsConstructor, ToString, Debug)]
+#[to_string(include_field_names = false)]
+pub struct Point<T> {
+    x: T,
+    y: T,
+}
+
+#[derive(AllArgsConstructor, ToString, Debug)]
+#[to_string(format = "{name} <{email}> {{{score:.1}}} {tags:?}")]
+pub struct Recipient {
     name: String,
     email: String,
-    hobby: Vec<String>,
+    score: f64,
+    tags: Vec<String>,
+}
+
+#[derive(AllArgsConstructor, Getter, Builder, RedactedDebug, ToString)]
+#[getter(vis = "pub")]
+pub struct Payment {
+    id: u32,
+    #[sensitive(with = mask_email)]
+    email: String,
+    #[sensitive(last = 4)]
+    card: String,
+    #[sensitive]
+    cvv: String,
+    #[sensitive]
+    token: Option<String>,
+}
+
+#[derive(AllArgsConstructor, Getter, EqualsAndHashCode, Debug)]
+#[getter(vis = "pub")]
+pub struct Article {
+    title: String,
+    tags: Vec<String>,
+    #[eq(skip)]
+    views: u64,
+    #[eq(skip)]
+    updated_at: u64,
+}
+
+#[derive(AllArgsConstructor, Getter, EqualsAndHashCode, Debug)]
+#[getter(vis = "pub")]
+pub struct Order {
+    #[eq(id)]
+    id: u64,
+    amount: f64,
+    updated_at: u64,
+}
+
+#[derive(AllArgsConstructor, EqualsAndHashCode, Debug)]
+pub struct Pair<K, V> {
+    #[eq(only)]
+    key: K,
+    #[eq(only)]
+    value: V,
+    #[allow(dead_code)]
+    note: String,
+}
+
+#[derive(Value, Clone, Debug)]
+pub struct Address {
+    #[new(into)]
+    street: String,
+    #[new(into)]
+    city: String,
+    zip: u32,
+    #[eq(skip)]
+    #[to_string(skip)]
+    #[with(skip)]
+    geocoded_at: u64,
+}
+
+#[derive(Getter, With, Clone, Debug, PartialEq)]
+#[getter(vis = "pub")]
+#[with(vis = "pub")]
+pub struct Money {
+    amount: i64,
+    currency: String,
+    #[with(skip)]
+    scale: u8,
+}
+
+#[derive(With, Debug)]
+#[with(vis = "pub")]
+pub struct Ticket<'a> {
+    pub id: u32,
+    pub title: &'a str,
+}
+
+#[derive(Getter, Debug)]
+#[getter(const, vis = "pub")]
+pub struct Version {
+    major: u32,
+    minor: u32,
+    #[getter(copy)]
+    stage: (char, u8),
+    tag: &'static str,
+    #[getter(copy = false)]
+    patch: u16,
+}
+
+pub const VERSION: Version = Version {
+    major: 0,
+    minor: 2,
+    stage: ('b', 1),
+    tag: "lombokrs",
+    patch: 0,
+};
+pub const VERSION_MAJOR: u32 = VERSION.get_major();
+
+#[derive(Data, Debug)]
+pub struct DataUser {
+    pub id: u32,
+    pub age: u8,
+    pub name: String,
+    pub email: String,
+    pub hobby: Vec<String>,
+}
+
+#[derive(Data, Debug)]
+#[getter(vis = "pub")]
+#[setter(chain, vis = "pub")]
+pub struct DataMember {
+    id: u32,
+    #[default = "guest"]
+    role: String,
+    #[builder(method = "activity")]
+    #[setter(collection = "activity")]
+    activities: Vec<String>,
+    #[getter(skip)]
+    #[setter(skip)]
+    password: String,
+}
+
+#[derive(Data, Debug)]
+#[data(setter = false, builder = false)]
+#[getter(vis = "pub")]
+pub struct DataSnapshot {
+    id: u32,
+    taken_at: u64,
+}
+
+// ----------------------------------------------------------------
+
+fn mask_email(email: &str) -> String {
+    match email.split_once('@') {
+        Some((name, domain)) => format!("{}***@{}", &name[..1], domain),
+        None => "***".to_string(),
+    }
 }
 
 // ----------------------------------------------------------------
 
-impl User {
-    pub fn new(
-        id: u32,
-        age: u8,
-        name: String,
-        email: String,
-        hobby: Vec<String>,
-        activities: Vec<String>,
-    ) -> Self {
+impl ViewModel {
+    pub fn changes(&self) -> Vec<String> {
+        self.changes.borrow().clone()
+    }
+
+    fn on_change<T: Debug + ?Sized>(&self, field: &'static str, old: &T, new: &T) {
+        self.changes
+            .borrow_mut()
+            .push(format!("{}: {:?} -> {:?}", field, old, new));
+    }
+
+    fn on_count_change(&self, field: &'static str, old: &dyn Any, new: &dyn Any) {
+        if let (Some(old), Some(new)) = (old.downcast_ref::<u32>(), new.downcast_ref::<u32>()) {
+            self.changes
+                .borrow_mut()
+                .push(format!("{}: {} -> {}", field, old, new));
+        }
+    }
+}
+
+// ----------------------------------------------------------------
+
+impl DataMember {
+    pub fn verify(&self, password: &str) -> bool {
+        self.password == password
+    }
+}
+
+// ----------------------------------------------------------------
+
+impl DataSnapshot {
+    pub fn new(id: u32, taken_at: u64) -> Self {
+        Self { id, taken_at }
+    }
+}
+
+// ----------------------------------------------------------------
+
+impl Account {
+    pub fn verify(&self, password: &str) -> bool {
+        self.password == password
+    }
+}
+
+// ----------------------------------------------------------------
+
+impl Session {
+    pub fn verify(&self, secret: &str) -> bool {
+        self.secret == secret
+    }
+}
+
+// ----------------------------------------------------------------
+
+impl Template {
+    pub fn new(source: String) -> Self {
         Self {
-            id,
-            age,
-            name,
-            email,
-            hobby,
-            activities,
+            source,
+            placeholders: OnceCell::new(),
+            parses: Cell::new(0),
         }
     }
+
+    pub fn parses(&self) -> u32 {
+        self.parses.get()
+    }
+
+    fn parse_placeholders(&self) -> Vec<String> {
+        self.parses.set(self.parses.get() + 1);
+
+        self.source
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}'))
+            .map(|(placeholder, _)| placeholder.to_string())
+            .collect()
+    }
 }
 
 // ----------------------------------------------------------------
 
-impl<'a> LifetimeUser<'a> {
-    pub fn new(id: u32, age: u8, name: &'a str, email: &'a str, hobby: Box<&'a str>) -> Self {
+impl Document {
+    pub fn new(text: String) -> Self {
         Self {
-            id,
-            age,
-            name,
-            email,
-            hobby,
+            text,
+            words: OnceLock::new(),
         }
     }
+
+    fn count_words(&self) -> usize {
+        self.text.split_whitespace().count()
+    }
 }
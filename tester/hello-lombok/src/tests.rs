@@ -18,7 +18,24 @@
 
 // ----------------------------------------------------------------
 
-use crate::{DataUser, User};
+use std::cell::{Cell, RefCell};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, AtomicU64};
+use std::sync::{Arc, Mutex, RwLock};
+
+use crate::inventory::Item;
+use crate::{
+    Account, Address, Article, Buf, Canvas, Config, Connection, ConnectionState, Contact, Counter,
+    Customer, DataMember, DataSnapshot, DataUser, Document, Endpoint, Feature, Form, Layer,
+    LifetimeUser, Money, Order, Pair, Payment, Playlist, Point, Profile, Recipient, Request, Row,
+    Server, Service, Session, Settings, Sketch, Team, Template, Ticket, Upload, User, ViewModel,
+    VERSION, VERSION_MAJOR,
+};
 
 #[test]
 fn test_setter() {
@@ -31,8 +48,8 @@ fn test_setter() {
         vec!["badminton".to_string()],
     );
 
-    assert_eq!(&10086u32, user.get_id());
-    assert_eq!(&18u8, user.get_age());
+    assert_eq!(10086u32, user.get_id());
+    assert_eq!(18u8, user.get_age());
 
     assert_eq!("photowey", user.get_name());
     assert_eq!("photowey@gmail.com", user.get_email());
@@ -46,8 +63,8 @@ fn test_setter() {
     user.set_email("lombokrs@gmail.com".to_string());
     user.set_hobby(vec!["football".to_string()]);
 
-    assert_eq!(&9527u32, user.get_id());
-    assert_eq!(&25u8, user.get_age());
+    assert_eq!(9527u32, user.get_id());
+    assert_eq!(25u8, user.get_age());
 
     assert_eq!("lombokrs", user.get_name());
     assert_eq!("lombokrs@gmail.com", user.get_email());
@@ -65,15 +82,15 @@ fn test_getter() {
         vec!["badminton".to_string()],
     );
 
-    assert_eq!(&10086u32, user.get_id());
-    assert_eq!(&18u8, user.get_age());
+    assert_eq!(10086u32, user.get_id());
+    assert_eq!(18u8, user.get_age());
 
     assert_eq!("photowey", user.get_name());
     assert_eq!("photowey@gmail.com", user.get_email());
     assert_eq!(&vec!["badminton".to_string()], user.get_hobby());
 
-    assert_eq!(&10086u32, user.id());
-    assert_eq!(&18u8, user.age());
+    assert_eq!(10086u32, user.id());
+    assert_eq!(18u8, user.age());
 
     assert_eq!("photowey", user.name());
     assert_eq!("photowey@gmail.com", user.email());
@@ -94,8 +111,8 @@ fn test_builder() {
         .build()
         .unwrap();
 
-    assert_eq!(&10086u32, user.get_id());
-    assert_eq!(&18u8, user.get_age());
+    assert_eq!(10086u32, user.get_id());
+    assert_eq!(18u8, user.get_age());
 
     assert_eq!("photowey", user.get_name());
     assert_eq!("photowey@gmail.com", user.get_email());
@@ -123,8 +140,8 @@ fn test_data() {
 
     // ----------------------------------------------------------------
 
-    assert_eq!(&9527u32, user.get_id());
-    assert_eq!(&25u8, user.get_age());
+    assert_eq!(9527u32, user.get_id());
+    assert_eq!(25u8, user.get_age());
 
     assert_eq!("lombokrs", user.get_name());
     assert_eq!("lombokrs@gmail.com", user.get_email());
@@ -132,8 +149,8 @@ fn test_data() {
 
     // ----------------------------------------------------------------
 
-    assert_eq!(&9527u32, user.id());
-    assert_eq!(&25u8, user.age());
+    assert_eq!(9527u32, user.id());
+    assert_eq!(25u8, user.age());
 
     assert_eq!("lombokrs", user.name());
     assert_eq!("lombokrs@gmail.com", user.email());
@@ -146,3 +163,918 @@ fn test_builder_default() {
     // Missing field: `id`!
     assert!(rvt.is_err());
 }
+
+#[test]
+fn test_builder_const_generics() {
+    // Non-`Clone` element type.
+    #[derive(Debug, PartialEq)]
+    struct Cell(u8);
+
+    let buf: Buf<Cell, 2> = Buf::builder()
+        .data([Cell(1), Cell(2)])
+        .len(2)
+        .build()
+        .unwrap();
+
+    assert_eq!(&[Cell(1), Cell(2)], buf.get_data());
+    assert_eq!(2usize, buf.get_len());
+}
+
+#[test]
+fn test_builder_type_changing() {
+    #[derive(Debug, PartialEq)]
+    struct Json(&'static str);
+
+    let request: Request<Json> = Request::builder()
+        .url("https://github.com/photowey/lombokrs".to_string())
+        .body(Json("{}"))
+        .headers(vec!["Accept: application/json".to_string()])
+        .build()
+        .unwrap();
+
+    assert_eq!("https://github.com/photowey/lombokrs", request.get_url());
+    assert_eq!(
+        &vec!["Accept: application/json".to_string()],
+        request.get_headers()
+    );
+    assert_eq!(&Json("{}"), request.get_body());
+
+    // ----------------------------------------------------------------
+
+    let rvt = Request::builder()
+        .url("https://github.com/photowey/lombokrs".to_string())
+        .build();
+    // Missing field: `body`!
+    assert!(rvt.is_err());
+}
+
+#[test]
+fn test_builder_type_changing_named_param() {
+    // `T: Clone` isn't switched, only `B` is.
+    let upload: Upload<'_, String, Vec<u8>> = Upload::builder()
+        .url("https://github.com/photowey/lombokrs")
+        .meta("avatar.png".to_string())
+        .body(vec![0x2a])
+        .build()
+        .unwrap();
+
+    assert_eq!("https://github.com/photowey/lombokrs", upload.get_url());
+    assert_eq!("avatar.png", upload.get_meta());
+    assert_eq!(&vec![0x2a], upload.get_body());
+}
+
+#[test]
+fn test_getter_deref() {
+    let profile = Profile {
+        nickname: "photowey".to_string(),
+        tags: vec!["rust".to_string()],
+        bio: Some("lombokrs".to_string()),
+        avatar: None,
+        score: Some(100),
+        home: PathBuf::from("/home/photowey"),
+        shared: Rc::new("shared".to_string()),
+        email: "photowey@gmail.com".to_string(),
+    };
+
+    let nickname: &str = profile.get_nickname();
+    let tags: &[String] = profile.get_tags();
+    let bio: Option<&str> = profile.get_bio();
+    let avatar: Option<&[u8]> = profile.get_avatar();
+    let score: Option<&u32> = profile.get_score();
+    let home: &Path = profile.get_home();
+    let shared: &String = profile.get_shared();
+    let email: &String = profile.get_email();
+
+    assert_eq!("photowey", nickname);
+    assert_eq!(&["rust".to_string()], tags);
+    assert_eq!(Some("lombokrs"), bio);
+    assert_eq!(None, avatar);
+    assert_eq!(Some(&100u32), score);
+    assert_eq!(Path::new("/home/photowey"), home);
+    assert_eq!("shared", shared);
+    assert_eq!("photowey@gmail.com", email);
+
+    assert_eq!("photowey", profile.nickname());
+}
+
+#[test]
+fn test_getter_copy_and_const() {
+    const MINOR: u32 = VERSION.minor();
+    const TAG: &str = VERSION.get_tag();
+
+    assert_eq!(0u32, VERSION_MAJOR);
+    assert_eq!(2u32, MINOR);
+    assert_eq!("lombokrs", TAG);
+    assert_eq!(('b', 1u8), VERSION.get_stage());
+    assert_eq!(&0u16, VERSION.get_patch());
+}
+
+#[test]
+fn test_getter_mut() {
+    let mut playlist = Playlist {
+        name: "lombokrs".to_string(),
+        tracks: vec!["a".to_string(), "b".to_string()],
+        cover: Some(vec![1, 2, 3]),
+        plays: 0,
+    };
+
+    playlist.get_name_mut().push_str("-rs");
+    *playlist.plays_mut() += 1;
+
+    let tracks: &mut [String] = playlist.get_tracks_mut();
+    tracks.swap(0, 1);
+
+    let cover: Option<&mut [u8]> = playlist.cover_mut();
+    cover.unwrap().reverse();
+
+    assert_eq!("lombokrs-rs", playlist.get_name());
+    assert_eq!(1u64, playlist.get_plays());
+    assert_eq!(&["b".to_string(), "a".to_string()], playlist.get_tracks());
+    assert_eq!(Some(&[3u8, 2, 1][..]), playlist.get_cover());
+}
+
+#[test]
+fn test_accessor_selection() {
+    let mut account = Account {
+        id: 10086,
+        name: "photowey".to_string(),
+        created_at: 1_700_000_000,
+        password: "secret".to_string(),
+    };
+
+    account.set_id(9527);
+    account.set_name("lombokrs".to_string());
+
+    assert_eq!(9527u32, account.id());
+    assert_eq!("lombokrs", account.get_name());
+    assert_eq!(1_700_000_000u64, account.created_at());
+    assert!(account.verify("secret"));
+
+    // ----------------------------------------------------------------
+
+    let mut session = Session {
+        token: "token".to_string(),
+        expires_at: 0,
+        secret: "secret".to_string(),
+    };
+
+    session.set_expires_at(3600);
+
+    assert_eq!("token", session.get_token());
+    assert_eq!("token", session.token());
+    assert_eq!(3600u64, session.expires_at);
+    assert!(session.verify("secret"));
+}
+
+#[test]
+fn test_accessor_visibility() {
+    let mut item = Item::new("lombokrs-0001".to_string(), 2, 10);
+
+    item.set_sku("lombokrs-0002".to_string());
+    item.restock(3);
+    item.reprice(20);
+
+    assert_eq!("lombokrs-0002", item.get_sku());
+    assert_eq!(5u32, item.count());
+    assert_eq!(100u32, item.total());
+    assert_eq!("lombokrs-0002 x 5", item.label());
+}
+
+#[test]
+fn test_getter_lazy() {
+    let template = Template::new("{name} <{email}>".to_string());
+
+    assert_eq!(0u32, template.parses());
+    assert_eq!(
+        &vec!["name".to_string(), "email".to_string()],
+        template.placeholders()
+    );
+    assert_eq!(2usize, template.get_placeholders().len());
+    assert_eq!(1u32, template.parses());
+
+    // ----------------------------------------------------------------
+
+    let document = Document::new("lombokrs is a lightweight Rust macro library".to_string());
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| assert_eq!(&7usize, document.words()));
+        }
+    });
+    assert_eq!(&7usize, document.get_words());
+}
+
+#[test]
+fn test_getter_predicate() {
+    let mut feature = Feature {
+        name: "lombokrs".to_string(),
+        enabled: true,
+        is_beta: false,
+        released: Some(2024),
+        dirty: false,
+    };
+
+    assert_eq!("lombokrs", feature.get_name());
+    assert!(feature.is_enabled());
+    assert!(feature.enabled());
+    assert!(!feature.is_beta());
+    assert_eq!(Some(2024u16), feature.is_released());
+    assert_eq!(Some(2024u16), feature.released());
+    assert!(!feature.get_dirty());
+
+    feature.set_enabled(false);
+    feature.set_is_beta(true);
+
+    assert!(!feature.is_enabled());
+    assert!(feature.is_beta());
+}
+
+#[test]
+fn test_option_accessors() {
+    let mut contact = Contact {
+        name: "photowey".to_string(),
+        email: None,
+        nickname: Some("lombokrs".to_string()),
+        age: None,
+    };
+
+    assert!(!contact.has_email());
+    assert_eq!(None, contact.email());
+    assert_eq!(
+        "unknown@gmail.com",
+        contact.email_or(&"unknown@gmail.com".to_string())
+    );
+
+    contact.set_email("photowey@gmail.com".to_string());
+    contact.set_age(18);
+
+    assert!(contact.has_email());
+    assert_eq!(Some(&"photowey@gmail.com".to_string()), contact.get_email());
+    assert_eq!(Some("lombokrs"), contact.nickname());
+    assert_eq!("lombokrs", contact.nickname_or("anonymous"));
+    assert_eq!(Some(18u8), contact.age());
+    assert_eq!(18u8, contact.age_or(0));
+
+    // ----------------------------------------------------------------
+
+    assert_eq!(Some("photowey@gmail.com".to_string()), contact.take_email());
+    assert!(!contact.has_email());
+
+    contact.clear_nickname();
+    contact.clear_age();
+
+    assert_eq!("anonymous", contact.nickname_or("anonymous"));
+    assert_eq!(0u8, contact.age_or(0));
+    assert_eq!("photowey", contact.name());
+}
+
+#[test]
+fn test_setter_chain() {
+    let mut settings = Settings {
+        theme: "light".to_string(),
+        font_size: 12,
+        proxy: None,
+        locale: "en".to_string(),
+        debug: false,
+    };
+
+    settings
+        .set_theme("dark".to_string())
+        .set_font_size(14)
+        .set_proxy("socks5://127.0.0.1:1080".to_string())
+        .locale("zh-CN".to_string())
+        .clear_proxy();
+    settings.set_debug(true);
+
+    assert_eq!("dark", settings.get_theme());
+    assert_eq!(14u8, settings.get_font_size());
+    assert_eq!(None, settings.get_proxy());
+    assert_eq!("zh-CN", settings.get_locale());
+    assert!(settings.is_debug());
+}
+
+#[test]
+fn test_setter_collection() {
+    let mut team = Team::builder()
+        .name("lombok".to_string())
+        .member("photowey".to_string())
+        .tags(BTreeSet::new())
+        .roles(HashMap::from([("photowey".to_string(), 1)]))
+        .backlog(VecDeque::new())
+        .build()
+        .unwrap();
+
+    team.add_member("rust".to_string())
+        .extend_members(vec!["cargo".to_string(), "clippy".to_string()])
+        .add_tag("macro".to_string())
+        .add_tag("derive".to_string())
+        .insert_role("rust".to_string(), 2)
+        .add_task("docs".to_string())
+        .add_task("tests".to_string());
+
+    assert_eq!("cargo", team.remove_member_at(2));
+    assert_eq!(&vec!["photowey", "rust", "clippy"], team.members());
+
+    assert!(team.remove_tag(&"macro".to_string()));
+    assert!(!team.remove_tag(&"macro".to_string()));
+    assert_eq!(1, team.tags().len());
+
+    assert_eq!(Some(1u8), team.remove_role(&"photowey".to_string()));
+    assert_eq!(Some(&2u8), team.roles().get("rust"));
+
+    assert_eq!(Some("docs".to_string()), team.remove_task_at(0));
+    assert_eq!(None, team.remove_task_at(1));
+
+    team.clear_members()
+        .clear_tags()
+        .clear_roles()
+        .clear_backlog();
+
+    assert!(team.members().is_empty());
+    assert!(team.tags().is_empty());
+    assert!(team.roles().is_empty());
+    assert!(team.backlog().is_empty());
+    assert_eq!("lombok", team.name());
+}
+
+#[test]
+fn test_builder_requires_non_vec_collections() {
+    // Only `Vec` fields may be left unset, the sets, maps and `VecDeque`s are required.
+    let rvt = Team::builder()
+        .name("lombok".to_string())
+        .tags(BTreeSet::new())
+        .backlog(VecDeque::new())
+        .build();
+    assert_eq!("Missing field: `roles`!", rvt.unwrap_err().to_string());
+
+    let team = Team::builder()
+        .name("lombok".to_string())
+        .tags(BTreeSet::new())
+        .roles(HashMap::new())
+        .backlog(VecDeque::new())
+        .build()
+        .unwrap();
+    assert!(team.members().is_empty());
+}
+
+#[test]
+fn test_setter_replace_and_update() {
+    let mut connection = Connection {
+        state: ConnectionState::Idle,
+        retries: 0,
+        peer: None,
+    };
+
+    assert_eq!(
+        ConnectionState::Idle,
+        connection.replace_state(ConnectionState::Connecting)
+    );
+    connection.update_retries(|retries| *retries += 1);
+    assert_eq!(None, connection.replace_peer("127.0.0.1".to_string()));
+
+    assert_eq!(
+        ConnectionState::Connecting,
+        connection.replace_state(ConnectionState::Connected)
+    );
+    connection.update_peer(|peer| peer.as_mut().unwrap().push_str(":8080"));
+
+    assert_eq!(ConnectionState::Connected, connection.state());
+    assert_eq!(1u32, connection.retries());
+    assert_eq!(Some(&"127.0.0.1:8080".to_string()), connection.peer());
+}
+
+#[test]
+fn test_interior_accessors() {
+    let service = Service {
+        hits: Cell::new(0),
+        banner: RefCell::new("hello".to_string()),
+        peers: Arc::new(Mutex::new(Vec::new())),
+        routes: RwLock::new(vec!["/".to_string()]),
+        running: AtomicBool::new(false),
+        requests: AtomicU64::new(0),
+    };
+
+    service
+        .set_hits(1)
+        .update_hits(|hits| *hits += 1)
+        .set_running(true)
+        .update_peers(|peers| peers.push("127.0.0.1".to_string()));
+
+    assert_eq!(2u32, service.hits());
+    assert!(service.is_running());
+    assert_eq!(0u64, service.replace_requests(10));
+    assert_eq!(10u64, service.requests());
+    assert_eq!("hello", service.replace_banner("world".to_string()));
+    assert_eq!("world", *service.banner());
+    assert_eq!(vec!["127.0.0.1"], *service.peers());
+
+    // poison = "recover"
+    let poisoned = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        service.update_routes(|_| panic!("poisoned"));
+    }));
+    assert!(poisoned.is_err());
+    assert!(service.routes.is_poisoned());
+
+    service.update_routes(|routes| routes.push("/health".to_string()));
+    assert_eq!(vec!["/", "/health"], *service.routes());
+}
+
+#[test]
+fn test_interior_is_opt_in() {
+    // No `interior`: the wrapper itself is read and written.
+    let mut counter = Counter::default();
+    counter.set_hits(Cell::new(1));
+
+    let hits: &Cell<u32> = counter.get_hits();
+    assert_eq!(1u32, hits.get());
+}
+
+#[test]
+fn test_track_changes() {
+    let mut row = Row::builder()
+        .id(1)
+        .name("photowey".to_string())
+        .email("photowey@gmail.com".to_string())
+        .build()
+        .unwrap();
+
+    assert!(!row.is_dirty());
+    assert_eq!(0, row.changed_fields().count());
+
+    row.set_name("lombokrs".to_string());
+    row.clear_email();
+    row.add_tag("rust".to_string());
+
+    assert!(row.is_dirty());
+    assert_eq!(
+        vec!["name", "email", "tags"],
+        row.changed_fields().collect::<Vec<_>>()
+    );
+
+    row.reset_changes();
+    assert!(!row.is_dirty());
+
+    row.set_id(2);
+    assert_eq!(vec!["id"], row.changed_fields().collect::<Vec<_>>());
+    assert_eq!(2u32, row.id());
+    assert_eq!(&vec!["rust"], row.tags());
+
+    // The dirty state is neither compared nor hashed, but cloned.
+    let mut clean = row.clone();
+    assert!(clean.is_dirty());
+    clean.reset_changes();
+    assert_eq!(row, clean);
+
+    let hash = |row: &Row| {
+        let mut hasher = DefaultHasher::new();
+        row.hash(&mut hasher);
+        hasher.finish()
+    };
+    assert_eq!(hash(&row), hash(&clean));
+}
+
+#[test]
+fn test_setter_notify() {
+    let mut view_model = ViewModel::default();
+
+    view_model.set_title("lombokrs".to_string());
+    view_model.set_subtitle("setters".to_string());
+    view_model.set_count(1);
+    view_model.clear_subtitle();
+    view_model.add_tag("ui".to_string());
+    view_model.update_title(|title| title.push('!'));
+    view_model.set_selected(true);
+
+    assert_eq!(
+        vec![
+            r#"title: "" -> "lombokrs""#,
+            r#"subtitle: None -> Some("setters")"#,
+            "count: 0 -> 1",
+            r#"subtitle: Some("setters") -> None"#,
+            r#"tags: [] -> ["ui"]"#,
+            r#"title: "lombokrs" -> "lombokrs!""#,
+            "selected: false -> true",
+        ],
+        view_model.changes()
+    );
+}
+
+#[test]
+fn test_change_listeners() {
+    let renders = Rc::new(RefCell::new(Vec::new()));
+
+    let mut form = Form::default();
+    let listener_renders = Rc::clone(&renders);
+    form.add_change_listener(move |field, old, new| {
+        if let (Some(old), Some(new)) = (old.downcast_ref::<String>(), new.downcast_ref::<String>())
+        {
+            listener_renders
+                .borrow_mut()
+                .push(format!("{}: {} -> {}", field, old, new));
+        } else {
+            listener_renders.borrow_mut().push(field.to_string());
+        }
+    });
+
+    form.set_title("signup".to_string());
+    form.add_field("email".to_string());
+    form.set_draft(true);
+
+    // A clone shares the listeners.
+    form.clone().clear_fields();
+
+    form.clear_change_listeners();
+    form.set_title("login".to_string());
+
+    assert_eq!(
+        vec!["title:  -> signup", "fields", "fields"],
+        *renders.borrow()
+    );
+    assert_eq!("login", form.title());
+}
+
+#[test]
+fn test_history() {
+    let mut canvas = Canvas::builder()
+        .color("white".to_string())
+        .width(1)
+        .build()
+        .unwrap();
+    let origin = canvas.checkpoint();
+
+    assert!(!canvas.undo());
+    assert_eq!(0, canvas.history_len());
+
+    canvas
+        .set_color("black".to_string())
+        .set_title("sketch".to_string());
+    let titled = canvas.checkpoint();
+    canvas.set_width(2).clear_title();
+
+    assert_eq!(3, canvas.history_len());
+
+    assert!(canvas.undo());
+    assert_eq!(Some("sketch"), canvas.title().map(String::as_str));
+    assert!(canvas.undo());
+    assert_eq!(1u32, canvas.width());
+    assert!(canvas.redo());
+    assert_eq!(2u32, canvas.width());
+
+    // A new change drops the undone ones.
+    canvas.set_width(4);
+    assert!(!canvas.redo());
+
+    assert!(canvas.rollback_to(titled));
+    assert_eq!(1u32, canvas.width());
+    assert_eq!("black", canvas.color());
+
+    // capacity = 3: the oldest change is gone.
+    assert!(!canvas.rollback_to(origin));
+    assert_eq!("black", canvas.color());
+    assert_eq!(0, canvas.history_len());
+
+    // take_x() and replace_x(..) record like every other setter.
+    canvas.set_title("sketch".to_string());
+    assert_eq!(Some("sketch".to_string()), canvas.take_title());
+    assert_eq!(1u32, canvas.replace_width(8));
+    assert!(canvas.undo());
+    assert_eq!(1u32, canvas.width());
+    assert!(canvas.undo());
+    assert_eq!(Some("sketch"), canvas.title().map(String::as_str));
+
+    // So do the collection methods.
+    canvas.add_layer("ink".to_string());
+    assert_eq!(&vec!["ink"], canvas.layers());
+    assert!(canvas.undo());
+    assert!(canvas.layers().is_empty());
+
+    // The history is cloned, but neither compared nor hashed.
+    let mut copy = canvas.clone();
+    assert_eq!(canvas.history_len(), copy.history_len());
+    assert!(copy.undo());
+    assert_eq!(None, copy.title());
+    assert!(copy.redo());
+    assert_eq!(canvas, copy);
+    assert!(HashSet::from([canvas, copy]).len() == 1);
+
+    // `T` appears in a skipped field only.
+    let mut layer: Layer<'_, String> = Layer::new();
+    layer.set_name("background");
+    assert!(layer.undo());
+    assert_eq!(None, layer.name());
+    assert_eq!(layer.clone(), Layer::new());
+}
+
+#[test]
+fn test_history_notifies() {
+    let changes = Rc::new(RefCell::new(Vec::new()));
+
+    let mut sketch = Sketch::default();
+    let listener_changes = Rc::clone(&changes);
+    sketch.add_change_listener(move |field, old, new| {
+        let (old, new) = match (old.downcast_ref::<String>(), new.downcast_ref::<String>()) {
+            (Some(old), Some(new)) => (old.clone(), new.clone()),
+            _ => (
+                old.downcast_ref::<u32>().unwrap().to_string(),
+                new.downcast_ref::<u32>().unwrap().to_string(),
+            ),
+        };
+        listener_changes
+            .borrow_mut()
+            .push(format!("{}: {} -> {}", field, old, new));
+    });
+
+    sketch.set_title("draft".to_string());
+    sketch.set_strokes(3);
+    assert!(sketch.undo());
+    assert!(sketch.undo());
+    assert!(sketch.redo());
+
+    assert_eq!(
+        vec![
+            "title:  -> draft",
+            "strokes: 0 -> 3",
+            "strokes: 3 -> 0",
+            "title: draft -> ",
+            "title:  -> draft",
+        ],
+        *changes.borrow()
+    );
+}
+
+#[test]
+fn test_history_restores_changes() {
+    let mut sketch = Sketch::default();
+
+    sketch.set_title("draft".to_string());
+    sketch.set_strokes(3);
+    let stroked = sketch.checkpoint();
+    sketch.set_strokes(4);
+
+    // Undoing every change puts back the clean state.
+    assert!(sketch.undo());
+    assert!(sketch.undo());
+    assert!(sketch.undo());
+    assert!(!sketch.is_dirty());
+
+    assert!(sketch.redo());
+    assert_eq!(vec!["title"], sketch.changed_fields().collect::<Vec<_>>());
+    assert!(sketch.redo());
+    assert!(sketch.redo());
+    assert!(sketch.rollback_to(stroked));
+    assert_eq!(
+        vec!["title", "strokes"],
+        sketch.changed_fields().collect::<Vec<_>>()
+    );
+
+    // A change recorded before `reset_changes()` marks the field when undone.
+    sketch.reset_changes();
+    assert!(sketch.undo());
+    assert_eq!(0u32, sketch.strokes());
+    assert_eq!(vec!["strokes"], sketch.changed_fields().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_all_args_constructor() {
+    let endpoint = Endpoint::create("localhost", 8080, Some("/health".to_string()));
+
+    assert_eq!("localhost", endpoint.host());
+    assert_eq!(8080u16, endpoint.port());
+    assert_eq!(Some(&"/health".to_string()), endpoint.path());
+
+    let endpoint = Endpoint::create("127.0.0.1".to_string(), 80, None);
+    assert_eq!(None, endpoint.path());
+
+    let user = LifetimeUser::new(
+        1,
+        18,
+        "photowey",
+        "photowey@gmail.com",
+        Box::new("badminton"),
+    );
+    assert_eq!("photowey", user.name());
+}
+
+#[test]
+fn test_required_args_constructor() {
+    let server = Server::new("localhost");
+
+    assert_eq!("localhost", server.host());
+    assert_eq!(8080u16, server.port());
+    assert_eq!(0usize, server.workers());
+    assert!(server.tags().is_empty());
+    assert_eq!(None, server.proxy());
+
+    // The builder shares the rule: only `host` is required.
+    let server = Server::builder()
+        .host("127.0.0.1".to_string())
+        .workers(4)
+        .build()
+        .unwrap();
+    assert_eq!(8080u16, server.port());
+    assert_eq!(4usize, server.workers());
+
+    assert!(Server::builder().port(80).build().is_err());
+}
+
+#[test]
+fn test_no_args_constructor() {
+    let config = Config::new();
+
+    assert_eq!("anonymous", config.name());
+    assert_eq!(8080u16, config.port());
+    assert_eq!(&vec!["en".to_string()], config.locales());
+    assert_eq!(Some(&30u64), config.timeout());
+    assert_eq!(0u8, config.retries());
+    assert!(!config.is_verbose());
+
+    let config = Config::default();
+    assert_eq!(8080u16, config.port());
+
+    // The builder shares `#[default = expr]`.
+    // `#[default]` leaves `retries` optional too.
+    let config = Config::builder().port(80).verbose(true).build().unwrap();
+    assert_eq!("anonymous", config.name());
+    assert_eq!(80u16, config.port());
+    assert_eq!(&vec!["en".to_string()], config.locales());
+    assert_eq!(Some(&30u64), config.timeout());
+    assert_eq!(0u8, config.retries());
+    assert!(config.is_verbose());
+
+    // Setting a defaulted collection replaces the default, it doesn't extend it.
+    let config = Config::builder()
+        .locales(vec!["fr".to_string()])
+        .verbose(true)
+        .build()
+        .unwrap();
+    assert_eq!(&vec!["fr".to_string()], config.locales());
+}
+
+#[test]
+fn test_to_string() {
+    let customer = Customer::new(
+        10086,
+        "photowey".to_string(),
+        None,
+        vec!["rust".to_string()],
+        "secret".to_string(),
+    );
+    assert_eq!(
+        r#"Customer(id=10086, username=photowey, email=None, tags=["rust"])"#,
+        customer.to_string()
+    );
+
+    let point = Point::new(1.5, -2.0);
+    assert_eq!("Point(1.5, -2)", point.to_string());
+
+    let recipient = Recipient::new(
+        "photowey".to_string(),
+        "photowey@gmail.com".to_string(),
+        9.25,
+        vec!["vip".to_string()],
+    );
+    assert_eq!(
+        r#"photowey <photowey@gmail.com> {9.2} ["vip"]"#,
+        format!("{}", recipient)
+    );
+}
+
+#[test]
+fn test_sensitive() {
+    let payment = Payment::new(
+        10086,
+        "photowey@gmail.com".to_string(),
+        "4111111111111111".to_string(),
+        "123".to_string(),
+        Some("secret".to_string()),
+    );
+
+    assert_eq!(
+        "Payment { id: 10086, email: p***@gmail.com, card: ***1111, cvv: ***, token: *** }",
+        format!("{:?}", payment)
+    );
+    assert_eq!(
+        "Payment(id=10086, email=p***@gmail.com, card=***1111, cvv=***, token=***)",
+        payment.to_string()
+    );
+
+    // The builder prints whether a `#[sensitive]` field is set, never its value.
+    let builder = Payment::builder()
+        .id(10086)
+        .card("4111111111111111".to_string())
+        .token("secret".to_string());
+    assert_eq!(
+        "PaymentBuilder { id: Some(10086), email: None, card: Some(***), cvv: None, token: Some(***) }",
+        format!("{:?}", builder)
+    );
+}
+
+#[test]
+fn test_equals_and_hash_code() {
+    fn hash<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let article = Article::new("lombok".to_string(), vec![], 1, 1);
+    let viewed = Article::new("lombok".to_string(), vec![], 2, 2);
+    assert_eq!(article, viewed);
+    assert_eq!(hash(&article), hash(&viewed));
+    assert_ne!(article, Article::new("lombokrs".to_string(), vec![], 1, 1));
+
+    let articles: HashSet<Article> = [article, viewed].into_iter().collect();
+    assert_eq!(1, articles.len());
+
+    // Entity mode: `f64` fields don't get in the way of `Eq` and `Hash`.
+    let order = Order::new(10086, 9.9, 1);
+    let updated = Order::new(10086, 19.9, 2);
+    assert_eq!(order, updated);
+    assert_eq!(hash(&order), hash(&updated));
+    assert_ne!(order, Order::new(10087, 9.9, 1));
+
+    let pair = Pair::new("k", 1.5, "first".to_string());
+    assert_eq!(pair, Pair::new("k", 1.5, "second".to_string()));
+    assert_ne!(pair, Pair::new("k", 2.5, "first".to_string()));
+}
+
+#[test]
+fn test_value() {
+    let address = Address::new("Tianfu Ave", "Chengdu", 610000, 1);
+
+    assert_eq!("Tianfu Ave", address.get_street());
+    assert_eq!("Chengdu", address.get_city());
+    assert_eq!(610000u32, address.get_zip());
+
+    let moved = address.with_zip_cloned(610041);
+    assert_ne!(address, moved);
+    assert_eq!(address, Address::new("Tianfu Ave", "Chengdu", 610000, 2));
+
+    let addresses: HashSet<Address> = [
+        address.clone(),
+        Address::new("Tianfu Ave", "Chengdu", 610000, 3),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(1, addresses.len());
+
+    assert_eq!(
+        "Address(street=Tianfu Ave, city=Chengdu, zip=610041)",
+        moved.to_string()
+    );
+}
+
+#[test]
+fn test_data_attributes() {
+    let mut member = DataMember::builder()
+        .id(10086)
+        .activity("badminton".to_string())
+        .password("secret".to_string())
+        .build()
+        .unwrap();
+
+    assert_eq!(10086u32, member.get_id());
+    assert_eq!("guest", member.get_role());
+    assert_eq!(&vec!["badminton".to_string()], member.get_activities());
+    assert!(member.verify("secret"));
+
+    member
+        .set_role("admin".to_string())
+        .add_activity("rust".to_string());
+    assert_eq!("admin", member.get_role());
+    assert_eq!(2, member.get_activities().len());
+
+    // #[data(setter = false, builder = false)]
+    let snapshot = DataSnapshot::new(1, 1700000000);
+    assert_eq!(1u32, snapshot.get_id());
+    assert_eq!(1700000000u64, snapshot.get_taken_at());
+}
+
+#[test]
+fn test_with() {
+    let price = Money {
+        amount: 100,
+        currency: "CNY".to_string(),
+        scale: 2,
+    };
+
+    let discounted = price.with_amount_cloned(80);
+    let converted = discounted.with_currency("USD".to_string()).with_amount(11);
+
+    assert_eq!(100i64, price.amount());
+    assert_eq!(11i64, converted.amount());
+    assert_eq!("USD", converted.currency());
+    assert_eq!(2u8, converted.scale());
+
+    // ----------------------------------------------------------------
+
+    // Non-`Clone` types only get with_x().
+    let ticket = Ticket {
+        id: 1,
+        title: "lombokrs",
+    };
+    let ticket = ticket.with_id(2).with_title("lombokrs-rs");
+
+    assert_eq!(2u32, ticket.id);
+    assert_eq!("lombokrs-rs", ticket.title);
+}